@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS meatball_day (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    month INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS meatball_channel (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    channel_id TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meatball_role (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    role_id TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meatball_role_assignment (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    date TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
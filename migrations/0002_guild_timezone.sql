@@ -0,0 +1,4 @@
+CREATE TABLE guild_timezone (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    timezone TEXT NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE guild_date_order (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    date_order TEXT NOT NULL
+);
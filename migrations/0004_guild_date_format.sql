@@ -0,0 +1,4 @@
+CREATE TABLE guild_date_format (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    date_format TEXT NOT NULL
+);
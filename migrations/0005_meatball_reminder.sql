@@ -0,0 +1,6 @@
+CREATE TABLE meatball_reminder (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    subscriber_id TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id, subscriber_id)
+);
@@ -0,0 +1,46 @@
+CREATE TABLE meatball_profile (
+    user_id TEXT NOT NULL PRIMARY KEY,
+    month INTEGER NOT NULL,
+    day INTEGER NOT NULL
+);
+
+-- members who saved different days in different guilds keep the one they
+-- added most recently.
+INSERT INTO meatball_profile (user_id, month, day)
+SELECT user_id, month, day
+FROM meatball_day
+WHERE rowid IN (
+    SELECT MAX(rowid)
+    FROM meatball_day
+    GROUP BY user_id
+);
+
+CREATE TABLE meatball_membership (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES meatball_profile(user_id) ON DELETE CASCADE,
+    announce INTEGER NOT NULL DEFAULT 1,
+    role INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+INSERT INTO meatball_membership (guild_id, user_id)
+SELECT guild_id, user_id
+FROM meatball_day;
+
+DROP TABLE meatball_day;
+
+-- the shape every guild-level query already expects.
+CREATE VIEW meatball_day AS
+SELECT
+    m.guild_id,
+    m.user_id,
+    p.month,
+    p.day,
+    m.announce,
+    m.role
+FROM
+    meatball_membership as m
+JOIN
+    meatball_profile as p
+ON
+    m.user_id = p.user_id;
@@ -0,0 +1,12 @@
+CREATE TABLE guild_meatball_events (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    enabled INTEGER NOT NULL
+);
+
+CREATE TABLE meatball_scheduled_event (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    date TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
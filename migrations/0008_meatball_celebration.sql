@@ -0,0 +1,5 @@
+CREATE TABLE meatball_celebration (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    date TEXT NOT NULL
+);
@@ -0,0 +1,14 @@
+ALTER TABLE meatball_celebration ADD COLUMN granted_at TEXT;
+ALTER TABLE meatball_celebration ADD COLUMN revoked_at TEXT;
+ALTER TABLE meatball_celebration ADD COLUMN message_id TEXT;
+-- one of 'active', 'completed' or 'forgotten'
+ALTER TABLE meatball_celebration ADD COLUMN outcome TEXT NOT NULL DEFAULT 'completed';
+
+-- role assignments are now just the celebrations that haven't finished yet.
+INSERT INTO meatball_celebration (guild_id, user_id, date, outcome)
+SELECT guild_id, user_id, date, 'active'
+FROM meatball_role_assignment;
+
+DROP TABLE meatball_role_assignment;
+
+CREATE INDEX meatball_celebration_member ON meatball_celebration (guild_id, user_id, date);
@@ -0,0 +1,13 @@
+CREATE TABLE meatball_month_role (
+    guild_id TEXT NOT NULL,
+    month INTEGER NOT NULL,
+    role_id TEXT NOT NULL,
+    PRIMARY KEY (guild_id, month)
+);
+
+CREATE TABLE meatball_month_role_assignment (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    role_id TEXT NOT NULL,
+    PRIMARY KEY (guild_id, user_id, role_id)
+);
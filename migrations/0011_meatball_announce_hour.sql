@@ -0,0 +1,8 @@
+CREATE TABLE guild_announce_hour (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    hour INTEGER NOT NULL
+);
+
+-- celebrations that still need announcing once the guild's announcement hour
+-- comes around.
+ALTER TABLE meatball_celebration ADD COLUMN announce_pending BOOLEAN NOT NULL DEFAULT FALSE;
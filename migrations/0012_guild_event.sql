@@ -0,0 +1,11 @@
+CREATE TABLE guild_event (
+    guild_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    -- NULL for events that come around every year
+    year INTEGER,
+    month INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    last_announced TEXT,
+    PRIMARY KEY (guild_id, name)
+);
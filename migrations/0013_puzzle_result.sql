@@ -0,0 +1,16 @@
+CREATE TABLE puzzle_result (
+    message_id TEXT NOT NULL PRIMARY KEY,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    game TEXT NOT NULL,
+    -- days since the common era for games numbered by date
+    puzzle_number INTEGER NOT NULL,
+    score INTEGER,
+    max_score INTEGER,
+    failed BOOLEAN NOT NULL,
+    hard_mode BOOLEAN NOT NULL,
+    posted_at TEXT NOT NULL,
+    -- only the first post of each puzzle counts
+    UNIQUE (guild_id, user_id, game, puzzle_number)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE guild_puzzle_summary (
+    guild_id TEXT NOT NULL PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    hour INTEGER NOT NULL,
+    last_posted TEXT
+);
@@ -0,0 +1,7 @@
+-- games are enabled unless a guild turns them off.
+CREATE TABLE guild_puzzle_game (
+    guild_id TEXT NOT NULL,
+    game TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    PRIMARY KEY (guild_id, game)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE guild_puzzle_rule (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    pattern TEXT NOT NULL,
+    emoji TEXT NOT NULL
+);
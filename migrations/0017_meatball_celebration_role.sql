@@ -0,0 +1,11 @@
+-- members can now opt out of the role, so remember which role (if any) each
+-- celebration actually gave them. everyone so far was given the guild's role.
+ALTER TABLE meatball_celebration ADD COLUMN role_id TEXT;
+
+UPDATE meatball_celebration
+SET role_id = (
+    SELECT role_id
+    FROM meatball_role
+    WHERE meatball_role.guild_id = meatball_celebration.guild_id
+)
+WHERE outcome = 'active';
@@ -0,0 +1,10 @@
+-- the days 0006 dropped when it merged each member's days into one profile,
+-- so admins can see what changed. they're saved just before 0006 runs, since
+-- 0006 itself can't be changed any more.
+CREATE TABLE IF NOT EXISTS meatball_profile_conflict (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    month INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
@@ -1,5 +1,5 @@
 use poise::serenity_prelude as serenity;
-use std::future::Future;
+use std::{future::Future, sync::Arc};
 
 use color_eyre::{
     eyre::{eyre, ErrReport},
@@ -9,6 +9,7 @@ use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePoolOptions},
     SqlitePool,
 };
+use tokio::sync::{Mutex, Notify};
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info};
 
@@ -16,15 +17,25 @@ use crate::{config, wisps};
 
 pub struct Bot {
     pub db: SqlitePool,
+    /// Wakes the meatball assignment scheduler after the saved days change.
+    pub meatball_wake: Arc<Notify>,
+    /// Each guild's puzzle settings, cached for the message listener.
+    pub puzzle_settings: wisps::wordle::settings::PuzzleSettingsCache,
+    assignment_lock: Arc<Mutex<()>>,
+    member_sightings: Arc<Mutex<wisps::meatball::scheduled_events::MemberSightings>>,
     scheduler: JobScheduler,
 }
 
 pub type CommandContext<'a> = poise::Context<'a, Bot, ErrReport>;
+pub type ApplicationContext<'a> = poise::ApplicationContext<'a, Bot, ErrReport>;
 
 #[derive(Clone)]
 pub struct JobContext {
     pub(crate) ctx: serenity::Context,
     pub(crate) db: SqlitePool,
+    pub(crate) meatball_wake: Arc<Notify>,
+    pub(crate) assignment_lock: Arc<Mutex<()>>,
+    pub(crate) member_sightings: Arc<Mutex<wisps::meatball::scheduled_events::MemberSightings>>,
 }
 
 fn make_job<F, Fut>(name: &str, schedule: &str, callback: F, ctx: JobContext) -> Result<Job>
@@ -51,23 +62,63 @@ where
 }
 
 impl Bot {
+    pub(crate) fn job_context(&self, ctx: serenity::Context) -> JobContext {
+        JobContext {
+            ctx,
+            db: self.db.clone(),
+            meatball_wake: self.meatball_wake.clone(),
+            assignment_lock: self.assignment_lock.clone(),
+            member_sightings: self.member_sightings.clone(),
+        }
+    }
+
     async fn spawn_scheduler(&self, ctx: serenity::Context) -> Result<()> {
         info!("Spawning scheduler");
 
-        let job_ctx = JobContext {
-            ctx,
-            db: self.db.clone(),
-        };
+        let job_ctx = self.job_context(ctx);
+
+        tokio::spawn(wisps::meatball::jobs::run_assignment_scheduler(
+            job_ctx.clone(),
+        ));
 
+        // the assignment scheduler wakes itself up when needed, this is just
+        // a fallback in case it misses something.
         self.scheduler
             .add(make_job(
                 "meatball::update_role_assignments",
-                &config::meatball_assignment_schedule(),
+                &config::meatball_reconcile_schedule(),
                 wisps::meatball::jobs::update_role_assignments,
                 job_ctx.clone(),
             )?)
             .await?;
 
+        self.scheduler
+            .add(make_job(
+                "meatball::update_month_roles",
+                &config::meatball_month_role_schedule(),
+                wisps::meatball::jobs::update_month_roles,
+                job_ctx.clone(),
+            )?)
+            .await?;
+
+        self.scheduler
+            .add(make_job(
+                "events::announce_events",
+                &config::events_schedule(),
+                wisps::events::jobs::announce_events,
+                job_ctx.clone(),
+            )?)
+            .await?;
+
+        self.scheduler
+            .add(make_job(
+                "wordle::post_summaries",
+                &config::puzzle_summary_schedule(),
+                wisps::wordle::jobs::post_summaries,
+                job_ctx.clone(),
+            )?)
+            .await?;
+
         self.scheduler.start().await?;
 
         Ok(())
@@ -89,10 +140,24 @@ async fn event_handler(
         poise::Event::Message {
             new_message: message,
         } => {
-            if let Err(e) = wisps::wordle::listeners::dispatch(ctx, message).await {
+            if let Err(e) = wisps::wordle::listeners::dispatch(ctx, bot, message).await {
                 error!("Failure in message listeners: {e}");
             }
         }
+        poise::Event::MessageUpdate { event, .. } => {
+            if let Err(e) = wisps::wordle::listeners::dispatch_edit(ctx, bot, event).await {
+                error!("Failure in message edit listeners: {e}");
+            }
+        }
+        poise::Event::MessageDelete {
+            deleted_message_id, ..
+        } => {
+            if let Err(e) =
+                wisps::wordle::listeners::dispatch_delete(bot, *deleted_message_id).await
+            {
+                error!("Failure in message delete listeners: {e}");
+            }
+        }
         poise::Event::Ready {
             data_about_bot: ready,
         } => {
@@ -113,8 +178,15 @@ pub async fn run(token: &str) -> Result<()> {
         )
         .await?;
 
+    wisps::meatball::jobs::save_profile_conflicts(&db).await?;
+    sqlx::migrate!().run(&db).await?;
+
     let bot = Bot {
         db,
+        meatball_wake: Arc::new(Notify::new()),
+        puzzle_settings: wisps::wordle::settings::PuzzleSettingsCache::default(),
+        assignment_lock: Arc::new(Mutex::new(())),
+        member_sightings: Arc::default(),
         scheduler: JobScheduler::new().await?,
     };
 
@@ -31,6 +31,40 @@ pub fn testing_guild() -> Option<serenity::GuildId> {
     }
 }
 
-pub fn meatball_assignment_schedule() -> String {
-    env::var("MEATBALL_ASSIGNMENT_SCHEDULE").unwrap_or_else(|_| "*/10 * * * * *".to_owned())
+pub fn meatball_reconcile_schedule() -> String {
+    if let Ok(schedule) = env::var("MEATBALL_RECONCILE_SCHEDULE") {
+        return schedule;
+    }
+
+    // this used to be the polling schedule, so keep honouring it for
+    // deployments that haven't moved over yet.
+    if let Ok(schedule) = env::var("MEATBALL_ASSIGNMENT_SCHEDULE") {
+        warn!("$MEATBALL_ASSIGNMENT_SCHEDULE is deprecated, use $MEATBALL_RECONCILE_SCHEDULE");
+        return schedule;
+    }
+
+    "0 */30 * * * *".to_owned()
+}
+
+pub fn meatball_month_role_schedule() -> String {
+    env::var("MEATBALL_MONTH_ROLE_SCHEDULE").unwrap_or_else(|_| "0 0 * * * *".to_owned())
+}
+
+pub fn events_schedule() -> String {
+    env::var("EVENTS_SCHEDULE").unwrap_or_else(|_| "0 */15 * * * *".to_owned())
+}
+
+pub fn puzzle_streak_grace_hours() -> i64 {
+    env::var("PUZZLE_STREAK_GRACE_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse().ok())
+        .unwrap_or(12)
+}
+
+pub fn puzzle_summary_schedule() -> String {
+    env::var("PUZZLE_SUMMARY_SCHEDULE").unwrap_or_else(|_| "0 */15 * * * *".to_owned())
+}
+
+pub fn puzzle_rules_path() -> Option<String> {
+    env::var("PUZZLE_RULES_PATH").ok()
 }
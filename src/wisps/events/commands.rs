@@ -0,0 +1,303 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use color_eyre::{eyre::eyre, Result};
+use itertools::Itertools;
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use crate::{
+    bot::CommandContext,
+    wisps::meatball::{dates, settings},
+};
+
+/// How many events to show on each page of the list. Names can be up to 100
+/// characters, so this keeps a page under discord's message limit.
+const LIST_PAGE_SIZE: usize = 8;
+
+/// Name, channel, year (for one-off events), month and day.
+type EventRow = (String, String, Option<i32>, u32, u32);
+
+#[poise::command(slash_command, subcommands("add", "list", "remove"))]
+#[allow(clippy::unused_async)]
+pub async fn event(_ctx: CommandContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Add a server event to announce on the day.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn add(
+    ctx: CommandContext<'_>,
+    #[description = "The name of the event"]
+    #[max_length = 100]
+    name: String,
+    #[description = "The date of the event, e.g. March 3rd or 2024-03-03"]
+    #[autocomplete = "autocomplete_date"]
+    date: String,
+    #[description = "The channel to announce the event in"] channel: serenity::GuildChannel,
+    #[description = "Whether the event comes around every year"] yearly: Option<bool>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let yearly = yearly.unwrap_or(false);
+
+    if channel.kind != serenity::ChannelType::Text {
+        ctx.say("I can only announce in normal text channels.")
+            .await?;
+        return Ok(());
+    }
+
+    let order = settings::get_date_order(guild.id, &ctx.data().db).await?;
+    let Some(parsed) = dates::parse_date(&date, order) else {
+        ctx.say("I don't understand that date... :thinking:")
+            .await?;
+        return Ok(());
+    };
+
+    if !parsed.is_real() {
+        ctx.say("That's not a real date... :thinking:").await?;
+        return Ok(());
+    }
+
+    let today = local_today(guild.id, &ctx).await?;
+    let year = if yearly {
+        None
+    } else {
+        let date = match parsed.year {
+            Some(year) => NaiveDate::from_ymd_opt(year, parsed.month, parsed.day),
+            None => next_date(None, parsed.month, parsed.day, today),
+        };
+
+        match date {
+            Some(date) if date >= today => Some(date.year()),
+            Some(_) => {
+                ctx.say("That date has already been and gone!").await?;
+                return Ok(());
+            }
+            None => {
+                ctx.say("That's not a real date... :thinking:").await?;
+                return Ok(());
+            }
+        }
+    };
+
+    sqlx::query(include_str!("queries/event-add.sql"))
+        .bind(guild.id.to_string())
+        .bind(&name)
+        .bind(channel.id.to_string())
+        .bind(year)
+        .bind(parsed.month)
+        .bind(parsed.day)
+        .execute(&ctx.data().db)
+        .await?;
+
+    let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
+    let when = match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, parsed.month, parsed.day)
+            .map_or_else(String::new, |date| dates::format_date(format, date)),
+        None => format!(
+            "{} every year",
+            dates::format_day_month(format, parsed.month, parsed.day)
+        ),
+    };
+
+    ctx.say(format!(
+        "I will announce **{name}** in {} on {when}! :calendar:",
+        serenity::Mention::from(channel.id)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List this server's upcoming events.
+#[poise::command(slash_command)]
+pub async fn list(ctx: CommandContext<'_>) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let today = local_today(guild.id, &ctx).await?;
+    let events = get_upcoming_events(guild.id, today, &ctx).await?;
+
+    if events.is_empty() {
+        ctx.say("There are no upcoming events!").await?;
+        return Ok(());
+    }
+
+    let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
+    let lines = events
+        .into_iter()
+        .map(|(date, (name, channel, year, _, _))| {
+            let channel = channel.parse::<u64>().map_or_else(
+                |_| channel.clone(),
+                |channel| serenity::Mention::from(serenity::ChannelId(channel)).to_string(),
+            );
+            format!(
+                "**{name}** on {}{}, {} in {channel}",
+                dates::format_date(format, date),
+                if year.is_none() { " (every year)" } else { "" },
+                describe_days_until(date, today)
+            )
+        })
+        .collect_vec();
+
+    let pages = lines
+        .chunks(LIST_PAGE_SIZE)
+        .map(|chunk| chunk.join("\n"))
+        .collect_vec();
+    let pages = pages.iter().map(String::as_str).collect_vec();
+
+    poise::builtins::paginate(ctx, &pages).await?;
+
+    Ok(())
+}
+
+/// Remove a server event.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn remove(
+    ctx: CommandContext<'_>,
+    #[description = "The name of the event"]
+    #[autocomplete = "autocomplete_event"]
+    name: String,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let result = sqlx::query(include_str!("queries/event-remove.sql"))
+        .bind(guild.id.to_string())
+        .bind(&name)
+        .execute(&ctx.data().db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        ctx.say(format!("I don't have an event called **{name}**."))
+            .await?;
+    } else {
+        ctx.say(format!("I have removed **{name}**.")).await?;
+    }
+
+    Ok(())
+}
+
+/// Count down to a server event, or the next one coming up.
+#[poise::command(slash_command)]
+pub async fn countdown(
+    ctx: CommandContext<'_>,
+    #[description = "The name of the event"]
+    #[autocomplete = "autocomplete_event"]
+    name: Option<String>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let today = local_today(guild.id, &ctx).await?;
+    let events = get_upcoming_events(guild.id, today, &ctx).await?;
+
+    let event = match &name {
+        Some(name) => events
+            .into_iter()
+            .find(|(_, (event, _, _, _, _))| event.eq_ignore_ascii_case(name)),
+        None => events.into_iter().next(),
+    };
+
+    let response = match (event, name) {
+        (Some((date, (name, _, _, _, _))), _) if date == today => {
+            format!("**{name}** is today! :tada:")
+        }
+        (Some((date, (name, _, _, _, _))), _) => {
+            let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
+            format!(
+                "**{name}** is {}, on {}! :hourglass_flowing_sand:",
+                describe_days_until(date, today),
+                dates::format_date(format, date)
+            )
+        }
+        (None, Some(name)) => format!("I don't have an upcoming event called **{name}**."),
+        (None, None) => "There are no upcoming events!".to_owned(),
+    };
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+#[allow(clippy::unused_async)]
+async fn autocomplete_date<'a>(
+    _ctx: CommandContext<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    dates::complete_month_names(partial).into_iter()
+}
+
+async fn autocomplete_event(
+    ctx: CommandContext<'_>,
+    partial: &str,
+) -> impl Iterator<Item = String> {
+    let Some(guild) = ctx.guild_id() else {
+        return vec![].into_iter();
+    };
+
+    let names: Vec<(String,)> = sqlx::query_as(include_str!("queries/event-names.sql"))
+        .bind(guild.to_string())
+        .bind(format!("{}%", escape_like(partial)))
+        .fetch_all(&ctx.data().db)
+        .await
+        .unwrap_or_else(|e| {
+            error!("Failed to autocomplete event names: {e}");
+            vec![]
+        });
+
+    names
+        .into_iter()
+        .map(|(name,)| name)
+        .collect_vec()
+        .into_iter()
+}
+
+/// Escapes LIKE wildcards so they match literally.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+async fn local_today(guild: serenity::GuildId, ctx: &CommandContext<'_>) -> Result<NaiveDate> {
+    let timezone = settings::get_timezone(guild, &ctx.data().db).await?;
+    Ok(Utc::now().with_timezone(&timezone).date_naive())
+}
+
+/// The guild's events that haven't happened yet, soonest first.
+async fn get_upcoming_events(
+    guild: serenity::GuildId,
+    today: NaiveDate,
+    ctx: &CommandContext<'_>,
+) -> Result<Vec<(NaiveDate, EventRow)>> {
+    let rows: Vec<EventRow> = sqlx::query_as(include_str!("queries/event-list.sql"))
+        .bind(guild.to_string())
+        .fetch_all(&ctx.data().db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let (name, _, year, month, day) = &row;
+            let date = next_date(*year, *month, *day, today);
+            if date.is_none() && year.is_none() {
+                warn!("Skipping invalid event {name}: {month}/{day}");
+            }
+            date.map(|date| (date, row))
+        })
+        .sorted_by_key(|(date, _)| *date)
+        .collect())
+}
+
+/// The next time an event happens, counting today. One-off events that have
+/// already happened have no next date.
+fn next_date(year: Option<i32>, month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day).filter(|date| *date >= today),
+        None => dates::next_occurrence(month, day, today.pred_opt()?),
+    }
+}
+
+fn describe_days_until(date: NaiveDate, today: NaiveDate) -> String {
+    match (date - today).num_days() {
+        0 => "today".to_owned(),
+        1 => "tomorrow".to_owned(),
+        days => format!("in {days} days"),
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{Datelike, NaiveDate, Timelike, Utc};
+use color_eyre::Result;
+use poise::serenity_prelude as serenity;
+use tracing::{error, info};
+
+use crate::{bot::JobContext, wisps::meatball::jobs::parse_timezone};
+
+/// Guild, name, channel, year (for one-off events), month, day, the last day
+/// it was announced, the guild's timezone and its announcement hour.
+type GuildEventRow = (
+    String,
+    String,
+    String,
+    Option<i32>,
+    u32,
+    u32,
+    Option<NaiveDate>,
+    String,
+    u32,
+);
+
+/// Announces every event that's happening today once the guild's announcement
+/// hour has come around. One-off events are removed once they've been
+/// announced, or once their day has passed, e.g. while the bot was down.
+pub async fn announce_events(ctx: JobContext) -> Result<()> {
+    let rows: Vec<GuildEventRow> = sqlx::query_as(include_str!("queries/get_guild_events.sql"))
+        .fetch_all(&ctx.db)
+        .await?;
+
+    let now = Utc::now();
+    for (guild, name, channel, year, month, day, last_announced, timezone, hour) in rows {
+        let local = now.with_timezone(&parse_timezone(&timezone));
+        let today = local.date_naive();
+
+        let passed = year
+            .and_then(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .is_some_and(|date| date < today);
+        if passed {
+            info!("Removing past event {name} from guild {guild}");
+            if let Err(e) = remove_event(&guild, &name, &ctx).await {
+                error!("Failed to remove past event {name} in guild {guild}: {e}");
+            }
+            continue;
+        }
+
+        let due = year.map_or(true, |year| year == today.year())
+            && today.month() == month
+            && today.day() == day
+            && local.hour() >= hour
+            && last_announced != Some(today);
+
+        if due {
+            if let Err(e) = announce_event(&guild, &name, &channel, year, today, &ctx).await {
+                error!("Failed to announce event {name} in guild {guild}: {e}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn announce_event(
+    guild: &str,
+    name: &str,
+    channel: &str,
+    year: Option<i32>,
+    today: NaiveDate,
+    ctx: &JobContext,
+) -> Result<()> {
+    let channel = serenity::ChannelId(channel.parse()?);
+    info!("Announcing event {name} in channel '{channel}'");
+
+    channel
+        .send_message(&ctx.ctx.http, |message| {
+            message
+                .content(format!("Today is **{name}**! :tada:"))
+                .allowed_mentions(|mentions| mentions.empty_parse())
+        })
+        .await?;
+
+    if year.is_some() {
+        remove_event(guild, name, ctx).await?;
+    } else {
+        sqlx::query(include_str!("queries/mark_guild_event_announced.sql"))
+            .bind(today)
+            .bind(guild)
+            .bind(name)
+            .execute(&ctx.db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn remove_event(guild: &str, name: &str, ctx: &JobContext) -> Result<()> {
+    sqlx::query(include_str!("queries/event-remove.sql"))
+        .bind(guild)
+        .bind(name)
+        .execute(&ctx.db)
+        .await?;
+    Ok(())
+}
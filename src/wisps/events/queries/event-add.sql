@@ -0,0 +1,16 @@
+INSERT
+INTO guild_event(
+    guild_id,
+    name,
+    channel_id,
+    year,
+    month,
+    day
+)
+VALUES(?, ?, ?, ?, ?, ?)
+ON CONFLICT(guild_id, name) DO UPDATE SET
+    channel_id = excluded.channel_id,
+    year = excluded.year,
+    month = excluded.month,
+    day = excluded.day,
+    last_announced = NULL
@@ -0,0 +1,10 @@
+SELECT
+    name,
+    channel_id,
+    year,
+    month,
+    day
+FROM
+    guild_event
+WHERE
+    guild_id = ?
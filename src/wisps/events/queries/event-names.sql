@@ -0,0 +1,9 @@
+SELECT
+    name
+FROM
+    guild_event
+WHERE
+    guild_id = ? AND name LIKE ? ESCAPE '\'
+ORDER BY
+    name
+LIMIT 25
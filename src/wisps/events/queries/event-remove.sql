@@ -0,0 +1,5 @@
+DELETE
+FROM
+    guild_event
+WHERE
+    guild_id = ? AND name = ?
@@ -0,0 +1,20 @@
+SELECT
+    ge.guild_id,
+    ge.name,
+    ge.channel_id,
+    ge.year,
+    ge.month,
+    ge.day,
+    ge.last_announced,
+    COALESCE(gt.timezone, 'UTC'),
+    COALESCE(gah.hour, 0)
+FROM
+    guild_event as ge
+LEFT JOIN
+    guild_timezone as gt
+ON
+    ge.guild_id = gt.guild_id
+LEFT JOIN
+    guild_announce_hour as gah
+ON
+    ge.guild_id = gah.guild_id
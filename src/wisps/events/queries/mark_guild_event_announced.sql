@@ -0,0 +1,6 @@
+UPDATE
+    guild_event
+SET
+    last_announced = ?
+WHERE
+    guild_id = ? AND name = ?
@@ -1,19 +1,41 @@
-use chrono::{Datelike, TimeZone};
+use std::time::Duration;
+
+use chrono::Datelike;
 use color_eyre::{eyre::eyre, Result};
 use itertools::{iproduct, Itertools};
-use poise::serenity_prelude as serenity;
-use tracing::warn;
+use poise::{serenity_prelude as serenity, Modal};
+use sqlx::SqliteExecutor;
+use tracing::{error, warn};
 
-use crate::bot::CommandContext;
+use super::{
+    dates::{self, DateFormat, DateOrder, ParsedDate},
+    jobs, settings,
+};
+use crate::bot::{ApplicationContext, CommandContext};
 
-// January 2nd, 2023
-const DATE_FORMAT: &str = "%B %d, %Y";
-// January 2nd
-const DAY_MONTH_FORMAT: &str = "%B %d";
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[poise::command(
     slash_command,
-    subcommands("lookup", "next", "save", "forget", "channel", "role")
+    subcommands(
+        "lookup",
+        "next",
+        "stats",
+        "save",
+        "forget",
+        "unremind",
+        "optin",
+        "profile",
+        "channel",
+        "role",
+        "timezone",
+        "announcehour",
+        "dateorder",
+        "dateformat",
+        "events",
+        "audit",
+        "monthrole"
+    )
 )]
 #[allow(clippy::unused_async)]
 pub async fn meatball(_ctx: CommandContext<'_>) -> Result<()> {
@@ -26,8 +48,18 @@ pub async fn lookup(
     ctx: CommandContext<'_>,
     #[description = "The user to lookup (defaults to you)"] user: Option<serenity::User>,
 ) -> Result<()> {
-    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
     let user_id = user.map_or_else(|| ctx.author().id, |u| u.id);
+    say_meatball_day(ctx, user_id).await
+}
+
+/// Find a user's meatball day from their profile.
+#[poise::command(context_menu_command = "Meatball day")]
+pub async fn lookup_user(ctx: CommandContext<'_>, user: serenity::User) -> Result<()> {
+    say_meatball_day(ctx, user.id).await
+}
+
+async fn say_meatball_day(ctx: CommandContext<'_>, user_id: serenity::UserId) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
 
     let row: Option<(u32, u32)> = sqlx::query_as(include_str!("queries/meatball-lookup.sql"))
         .bind(guild.id.to_string())
@@ -36,14 +68,11 @@ pub async fn lookup(
         .await?;
 
     let response = if let Some((month, day)) = row {
-        let date = chrono::Utc
-            .with_ymd_and_hms(2000, month, day, 0, 0, 0)
-            .earliest()
-            .ok_or(eyre!("Failed to create dummy date for lookup return"))?;
+        let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
         format!(
             "{}'s meatball day is on {}",
             serenity::Mention::from(user_id),
-            date.format(DAY_MONTH_FORMAT)
+            dates::format_day_month(format, month, day)
         )
     } else {
         format!(
@@ -57,13 +86,85 @@ pub async fn lookup(
     Ok(())
 }
 
+/// Get a DM on the message author's meatball day.
+#[poise::command(context_menu_command = "Remind me of their meatball day", ephemeral)]
+pub async fn remind_me(ctx: CommandContext<'_>, message: serenity::Message) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let user_id = message.author.id;
+
+    sqlx::query(include_str!("queries/meatball-remind.sql"))
+        .bind(guild.id.to_string())
+        .bind(user_id.to_string())
+        .bind(ctx.author().id.to_string())
+        .execute(&ctx.data().db)
+        .await?;
+
+    let saved: Option<(u32, u32)> = sqlx::query_as(include_str!("queries/meatball-lookup.sql"))
+        .bind(guild.id.to_string())
+        .bind(user_id.to_string())
+        .fetch_optional(&ctx.data().db)
+        .await?;
+
+    let response = if saved.is_some() {
+        format!(
+            "I'll send you a DM on {}'s meatball day! :bell:",
+            serenity::Mention::from(user_id)
+        )
+    } else {
+        format!(
+            "{} hasn't saved their meatball day yet, but I'll DM you if they do! :bell:",
+            serenity::Mention::from(user_id)
+        )
+    };
+
+    ctx.say(format!(
+        "{response}\nUse `/meatball unremind` if you change your mind."
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop getting a DM on a user's meatball day.
+#[poise::command(slash_command, ephemeral)]
+pub async fn unremind(
+    ctx: CommandContext<'_>,
+    #[description = "The user to stop reminding you about"] user: serenity::User,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let result = sqlx::query(include_str!("queries/meatball-unremind.sql"))
+        .bind(guild.id.to_string())
+        .bind(user.id.to_string())
+        .bind(ctx.author().id.to_string())
+        .execute(&ctx.data().db)
+        .await?;
+
+    let response = if result.rows_affected() == 0 {
+        format!(
+            "I wasn't reminding you about {}'s meatball day.",
+            serenity::Mention::from(user.id)
+        )
+    } else {
+        format!(
+            "I won't remind you about {}'s meatball day any more. :no_bell:",
+            serenity::Mention::from(user.id)
+        )
+    };
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
 /// Find the next occurring meatball day.
 #[poise::command(slash_command)]
 pub async fn next(ctx: CommandContext<'_>) -> Result<()> {
     let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
 
-    let now = chrono::Utc::now();
-    let years = [now.year(), now.year() + 1].into_iter();
+    let timezone = settings::get_timezone(guild.id, &ctx.data().db).await?;
+    let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+    let years = [today.year(), today.year() + 1].into_iter();
 
     let rows: Vec<(String, u32, u32)> = sqlx::query_as(include_str!("queries/meatball-next.sql"))
         .bind(guild.id.to_string())
@@ -72,9 +173,7 @@ pub async fn next(ctx: CommandContext<'_>) -> Result<()> {
 
     let mut meatball_days = iproduct!(rows, years)
         .map(|((user, month, day), year)| {
-            let date = chrono::Utc
-                .with_ymd_and_hms(year, month, day, 0, 0, 0)
-                .earliest();
+            let date = chrono::NaiveDate::from_ymd_opt(year, month, day);
 
             if date.is_none() {
                 warn!("Skipping {user}'s invalid meatball day: {month}/{day}");
@@ -85,11 +184,12 @@ pub async fn next(ctx: CommandContext<'_>) -> Result<()> {
         .filter_map(|(user, date)| date.map(|date| (user, date)))
         .sorted_by(|(_, a), (_, b)| Ord::cmp(a, b));
 
-    let response = if let Some((user, date)) = meatball_days.find(|(_, date)| *date > now) {
+    let response = if let Some((user, date)) = meatball_days.find(|(_, date)| *date > today) {
+        let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
         format!(
             "The next meatball day is {}'s on {}! :alarm_clock:",
             serenity::Mention::from(serenity::UserId(user.parse()?)),
-            date.format(DATE_FORMAT)
+            dates::format_date(format, date)
         )
     } else {
         "I have no meatball days saved!".to_owned()
@@ -100,57 +200,542 @@ pub async fn next(ctx: CommandContext<'_>) -> Result<()> {
     Ok(())
 }
 
-const fn days_in_month(month: i64) -> i64 {
-    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-    [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31][month as usize - 1]
+/// How many shared days to list in the stats, keeping them within a message.
+const STATS_SHARED_DAYS: usize = 10;
+
+/// Show some statistics about this server's meatball days.
+#[poise::command(slash_command)]
+pub async fn stats(ctx: CommandContext<'_>) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let days: Vec<(u32, u32, u32)> = sqlx::query_as(include_str!("queries/meatball-stats.sql"))
+        .bind(guild.id.to_string())
+        .fetch_all(&ctx.data().db)
+        .await?;
+
+    let (celebrations,): (u32,) =
+        sqlx::query_as(include_str!("queries/meatball-stats-celebrations.sql"))
+            .bind(guild.id.to_string())
+            .fetch_one(&ctx.data().db)
+            .await?;
+
+    if days.is_empty() {
+        ctx.say("I have no meatball days saved!").await?;
+        return Ok(());
+    }
+
+    let format = settings::get_date_format(guild.id, &ctx.data().db).await?;
+    let registered: u32 = days.iter().map(|(_, _, count)| count).sum();
+
+    let mut months = [0; 12];
+    for (month, _, count) in &days {
+        if let Some(total) = month
+            .checked_sub(1)
+            .and_then(|index| months.get_mut(index as usize))
+        {
+            *total += count;
+        }
+    }
+    let widest = months.iter().copied().max().unwrap_or(1);
+
+    let histogram = (1..=12)
+        .zip(months)
+        .map(|(month, count)| {
+            // scale the bars so the busiest month is 20 blocks wide.
+            let width = (count * 20).div_ceil(widest);
+            format!(
+                "{} {:<20} {count}",
+                dates::short_month_name(month),
+                "█".repeat(width as usize)
+            )
+        })
+        .join("\n");
+
+    let shared = days
+        .iter()
+        .filter(|(_, _, count)| *count > 1)
+        .sorted_by_key(|(month, day, count)| (std::cmp::Reverse(*count), *month, *day))
+        .collect_vec();
+
+    let mut response = format!(
+        "**{registered}** of **{}** members have saved their meatball day, \
+        and I have celebrated **{celebrations}** of them so far! :meatball:\n\
+        ```\n{histogram}\n```",
+        guild.member_count
+    );
+
+    if let Some((month, day, count)) = shared.first() {
+        response.push_str(&format!(
+            "\nThe busiest day is {} with {count} meatball days!",
+            dates::format_day_month(format, *month, *day)
+        ));
+        let clusters = shared
+            .iter()
+            .take(STATS_SHARED_DAYS)
+            .map(|(month, day, count)| {
+                format!(
+                    "{} ({count})",
+                    dates::format_day_month(format, *month, *day)
+                )
+            })
+            .join(", ");
+        response.push_str(&format!("\nShared days: {clusters}"));
+        if shared.len() > STATS_SHARED_DAYS {
+            response.push_str(&format!(" and {} more", shared.len() - STATS_SHARED_DAYS));
+        }
+    } else {
+        response.push_str("\nEveryone has their meatball day all to themselves.");
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+#[allow(clippy::unused_async)]
+async fn autocomplete_date<'a>(
+    _ctx: CommandContext<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    dates::complete_month_names(partial).into_iter()
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Save your meatball day"]
+struct SaveModal {
+    #[name = "Your meatball day"]
+    #[placeholder = "e.g. March 3rd, 3 Mar or 03/03"]
+    date: String,
+}
+
+/// Uses the date given to the command, or asks for one with a form.
+async fn date_or_modal(
+    ctx: ApplicationContext<'_>,
+    date: Option<String>,
+) -> Result<Option<String>> {
+    if date.is_some() {
+        return Ok(date);
+    }
+
+    Ok(SaveModal::execute(ctx).await?.map(|modal| modal.date))
+}
+
+/// Asks the author to confirm an action with a pair of buttons.
+/// Returns false if they cancel or don't answer in time.
+async fn confirm(ctx: CommandContext<'_>, prompt: String, action: &str) -> Result<bool> {
+    let id = ctx.id();
+    let confirm_id = format!("{id}-confirm");
+    let cancel_id = format!("{id}-cancel");
+
+    let reply = ctx
+        .send(|message| {
+            message.content(prompt).components(|components| {
+                components.create_action_row(|row| {
+                    row.create_button(|button| {
+                        button
+                            .custom_id(&confirm_id)
+                            .label(action)
+                            .style(serenity::ButtonStyle::Danger)
+                    })
+                    .create_button(|button| {
+                        button
+                            .custom_id(&cancel_id)
+                            .label("Cancel")
+                            .style(serenity::ButtonStyle::Secondary)
+                    })
+                })
+            })
+        })
+        .await?;
+
+    let interaction = serenity::CollectComponentInteraction::new(ctx.discord())
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(CONFIRM_TIMEOUT)
+        .filter(move |interaction| interaction.data.custom_id.starts_with(&id.to_string()))
+        .await;
+
+    // either way, the buttons have served their purpose.
+    let Some(interaction) = interaction else {
+        reply
+            .edit(ctx, |message| message.components(|components| components))
+            .await?;
+        ctx.say("I didn't hear back, so I haven't changed anything.")
+            .await?;
+        return Ok(false);
+    };
+
+    interaction
+        .create_interaction_response(ctx.discord(), |response| {
+            response
+                .kind(serenity::InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|data| data.components(|components| components))
+        })
+        .await?;
+
+    Ok(interaction.data.custom_id == confirm_id)
+}
+
+async fn get_profile(ctx: CommandContext<'_>) -> Result<Option<(u32, u32)>> {
+    Ok(sqlx::query_as(include_str!("queries/meatball-profile.sql"))
+        .bind(ctx.author().id.to_string())
+        .fetch_optional(&ctx.data().db)
+        .await?)
+}
+
+/// The date format to show to the author, which depends on where they asked.
+async fn get_date_format(ctx: CommandContext<'_>) -> Result<DateFormat> {
+    match ctx.guild_id() {
+        Some(guild) => settings::get_date_format(guild, &ctx.data().db).await,
+        None => Ok(DateFormat::default()),
+    }
+}
+
+/// Checks with the author before replacing a different saved day.
+async fn confirm_overwrite(ctx: CommandContext<'_>, date: ParsedDate) -> Result<bool> {
+    let Some((month, day)) = get_profile(ctx).await? else {
+        return Ok(true);
+    };
+
+    if (month, day) == (date.month, date.day) {
+        return Ok(true);
+    }
+
+    let format = get_date_format(ctx).await?;
+    let confirmed = confirm(
+        ctx,
+        format!(
+            "Your meatball day is already saved as {}. Do you want to change it to {}?",
+            dates::format_day_month(format, month, day),
+            dates::format_day_month(format, date.month, date.day),
+        ),
+        "Change it",
+    )
+    .await?;
+
+    if !confirmed {
+        ctx.say("Okay, I have left your meatball day alone.")
+            .await?;
+    }
+
+    Ok(confirmed)
+}
+
+/// Parses and validates a date given to one of the save commands, telling the
+/// user what's wrong with it if it's no good.
+async fn parse_meatball_day(ctx: CommandContext<'_>, input: &str) -> Result<Option<ParsedDate>> {
+    let order = match ctx.guild_id() {
+        Some(guild) => settings::get_date_order(guild, &ctx.data().db).await?,
+        None => DateOrder::default(),
+    };
+
+    let Some(date) = dates::parse_date(input, order) else {
+        ctx.say("I don't understand that date... :thinking:")
+            .await?;
+        return Ok(None);
+    };
+
+    if !(1..=12).contains(&date.month) {
+        ctx.say("That's not a real month... :thinking:").await?;
+        return Ok(None);
+    }
+
+    if !date.is_real() {
+        ctx.say("That's not a real day of the month... :thinking:")
+            .await?;
+        return Ok(None);
+    }
+
+    Ok(Some(date))
+}
+
+async fn save_profile(
+    user: serenity::UserId,
+    date: ParsedDate,
+    executor: impl SqliteExecutor<'_>,
+) -> Result<()> {
+    sqlx::query(include_str!("queries/meatball-save.sql"))
+        .bind(user.to_string())
+        .bind(date.month)
+        .bind(date.day)
+        .execute(executor)
+        .await?;
+    Ok(())
 }
 
 /// Save your meatball day.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, ephemeral)]
 pub async fn save(
-    ctx: CommandContext<'_>,
-    #[description = "The month of your meatball day"] month: i64,
-    #[description = "The day of your meatball day"] day: i64,
+    app_ctx: ApplicationContext<'_>,
+    #[description = "Your meatball day, e.g. \"March 3rd\" or \"03/03\". Leave empty to use a form"]
+    #[autocomplete = "autocomplete_date"]
+    date: Option<String>,
 ) -> Result<()> {
+    let ctx = CommandContext::Application(app_ctx);
     let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
 
-    if !(1..=12).contains(&month) {
-        ctx.say("That's not a real month... :thinking:").await?;
+    let Some(date) = date_or_modal(app_ctx, date).await? else {
+        return Ok(());
+    };
+
+    let Some(date) = parse_meatball_day(ctx, &date).await? else {
+        return Ok(());
+    };
+
+    if !confirm_overwrite(ctx, date).await? {
         return Ok(());
     }
 
-    let num_days = days_in_month(month);
-    if day < 1 || day > num_days {
-        ctx.say("That's not a real day of the month... :thinking:")
+    let mut tx = ctx.data().db.begin().await?;
+    save_profile(ctx.author().id, date, &mut tx).await?;
+    sqlx::query(include_str!("queries/meatball-join.sql"))
+        .bind(guild.id.to_string())
+        .bind(ctx.author().id.to_string())
+        .execute(&mut tx)
+        .await?;
+    tx.commit().await?;
+
+    // celebrating can mean a few trips to discord, which could take longer
+    // than it gives us to respond.
+    ctx.defer_ephemeral().await?;
+    let job_ctx = ctx.data().job_context(ctx.discord().clone());
+    let celebrated = jobs::celebrate_member(guild.id, ctx.author().id, &job_ctx)
+        .await
+        .unwrap_or_else(|e| {
+            // the scheduler will try again soon anyway.
+            error!("Failed to celebrate meatball day straight away: {e}");
+            false
+        });
+    // only wake the scheduler now, so it can't get to the member first.
+    ctx.data().meatball_wake.notify_one();
+
+    if celebrated {
+        ctx.say("I have registered your meatball day, and it's today! :partying_face:")
+            .await?;
+    } else {
+        ctx.say("I have registered your meatball day! :calendar:")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Stop celebrating your meatball day in this server.
+#[poise::command(slash_command, ephemeral)]
+pub async fn forget(ctx: CommandContext<'_>) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let saved: Option<(u32, u32)> = sqlx::query_as(include_str!("queries/meatball-lookup.sql"))
+        .bind(guild.id.to_string())
+        .bind(ctx.author().id.to_string())
+        .fetch_optional(&ctx.data().db)
+        .await?;
+
+    if saved.is_none() {
+        ctx.say("I'm not celebrating your meatball day in this server.")
             .await?;
         return Ok(());
     }
 
-    sqlx::query(include_str!("queries/meatball-save.sql"))
+    let confirmed = confirm(
+        ctx,
+        "Do you want me to stop celebrating your meatball day in this server?".to_owned(),
+        "Stop celebrating",
+    )
+    .await?;
+
+    if !confirmed {
+        ctx.say("Okay, I'll keep celebrating it! :partying_face:")
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(include_str!("queries/meatball-forget.sql"))
         .bind(guild.id.to_string())
         .bind(ctx.author().id.to_string())
-        .bind(month)
-        .bind(day)
         .execute(&ctx.data().db)
         .await?;
 
-    ctx.say("I have registered your meatball day! :calendar:")
-        .await?;
+    ctx.defer_ephemeral().await?;
+    let job_ctx = ctx.data().job_context(ctx.discord().clone());
+    if let Err(e) = jobs::revoke_member(guild.id, ctx.author().id, &job_ctx).await {
+        error!("Failed to revoke meatball role straight away: {e}");
+    }
+    ctx.data().meatball_wake.notify_one();
+
+    ctx.say(
+        "I won't celebrate your meatball day in this server any more. :boom:\n\
+        Use `/meatball profile forget` to remove it everywhere.",
+    )
+    .await?;
 
     Ok(())
 }
 
-/// Remove your meatball day.
-#[poise::command(slash_command)]
-pub async fn forget(ctx: CommandContext<'_>) -> Result<()> {
+/// Choose how your meatball day is celebrated in this server.
+#[poise::command(slash_command, ephemeral)]
+pub async fn optin(
+    ctx: CommandContext<'_>,
+    #[description = "Announce your meatball day in this server"] announce: bool,
+    #[description = "Give you the meatball role in this server"] role: bool,
+) -> Result<()> {
     let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
 
-    sqlx::query(include_str!("queries/meatball-forget.sql"))
+    if get_profile(ctx).await?.is_none() {
+        ctx.say("You need to save your meatball day first! :calendar:")
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(include_str!("queries/meatball-optin.sql"))
         .bind(guild.id.to_string())
         .bind(ctx.author().id.to_string())
+        .bind(announce)
+        .bind(role)
+        .execute(&ctx.data().db)
+        .await?;
+
+    // a role given out earlier today shouldn't outlast opting out of it.
+    if !role {
+        let job_ctx = ctx.data().job_context(ctx.discord().clone());
+        jobs::withdraw_role(guild.id, ctx.author().id, &job_ctx).await?;
+    }
+    ctx.data().meatball_wake.notify_one();
+
+    ctx.say("I have updated your meatball day settings for this server. :gear:")
+        .await?;
+
+    Ok(())
+}
+
+/// Manage your meatball day across every server, including from DMs.
+#[poise::command(
+    slash_command,
+    subcommands("profile_show", "profile_save", "profile_forget")
+)]
+#[allow(clippy::unused_async)]
+pub async fn profile(_ctx: CommandContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Show your meatball day and the servers celebrating it.
+#[poise::command(slash_command, rename = "show", ephemeral)]
+pub async fn profile_show(ctx: CommandContext<'_>) -> Result<()> {
+    let Some((month, day)) = get_profile(ctx).await? else {
+        ctx.say("You haven't saved a meatball day yet!").await?;
+        return Ok(());
+    };
+
+    let format = get_date_format(ctx).await?;
+
+    let guilds: Vec<(String, bool, bool)> =
+        sqlx::query_as(include_str!("queries/meatball-profile-guilds.sql"))
+            .bind(ctx.author().id.to_string())
+            .fetch_all(&ctx.data().db)
+            .await?;
+
+    let mut response = format!(
+        "Your meatball day is on {}",
+        dates::format_day_month(format, month, day)
+    );
+    if guilds.is_empty() {
+        response.push_str(", but no servers are celebrating it yet.");
+    }
+    for (guild, announce, role) in guilds {
+        let guild = serenity::GuildId(guild.parse()?);
+        let name = guild
+            .name(&ctx.discord().cache)
+            .unwrap_or_else(|| guild.to_string());
+        response.push_str(&format!(
+            "\n- {name}: announcements {}, role {}",
+            if announce { "on" } else { "off" },
+            if role { "on" } else { "off" },
+        ));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Save your meatball day without joining any servers.
+#[poise::command(slash_command, rename = "save", ephemeral)]
+pub async fn profile_save(
+    app_ctx: ApplicationContext<'_>,
+    #[description = "Your meatball day, e.g. \"March 3rd\" or \"03/03\". Leave empty to use a form"]
+    #[autocomplete = "autocomplete_date"]
+    date: Option<String>,
+) -> Result<()> {
+    let ctx = CommandContext::Application(app_ctx);
+
+    let Some(date) = date_or_modal(app_ctx, date).await? else {
+        return Ok(());
+    };
+
+    let Some(date) = parse_meatball_day(ctx, &date).await? else {
+        return Ok(());
+    };
+
+    if !confirm_overwrite(ctx, date).await? {
+        return Ok(());
+    }
+
+    save_profile(ctx.author().id, date, &ctx.data().db).await?;
+    ctx.data().meatball_wake.notify_one();
+
+    ctx.say(
+        "I have saved your meatball day! :calendar:\n\
+        Use `/meatball save` in a server to celebrate it there.",
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Remove your meatball day from every server.
+#[poise::command(slash_command, rename = "forget", ephemeral)]
+pub async fn profile_forget(ctx: CommandContext<'_>) -> Result<()> {
+    let user = ctx.author().id;
+
+    if get_profile(ctx).await?.is_none() {
+        ctx.say("You haven't saved a meatball day yet!").await?;
+        return Ok(());
+    }
+
+    let confirmed = confirm(
+        ctx,
+        "Do you want me to forget your meatball day in every server?".to_owned(),
+        "Forget it",
+    )
+    .await?;
+
+    if !confirmed {
+        ctx.say("Okay, I have left your meatball day alone.")
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(include_str!("queries/meatball-profile-forget.sql"))
+        .bind(user.to_string())
         .execute(&ctx.data().db)
         .await?;
 
+    let assignments: Vec<(String,)> =
+        sqlx::query_as(include_str!("queries/meatball-profile-assignments.sql"))
+            .bind(user.to_string())
+            .fetch_all(&ctx.data().db)
+            .await?;
+
+    ctx.defer_ephemeral().await?;
+    let job_ctx = ctx.data().job_context(ctx.discord().clone());
+    for (guild,) in assignments {
+        let guild = serenity::GuildId(guild.parse()?);
+        if let Err(e) = jobs::revoke_member(guild, user, &job_ctx).await {
+            error!("Failed to revoke meatball role in guild {guild} straight away: {e}");
+        }
+    }
+    ctx.data().meatball_wake.notify_one();
+
     ctx.say("I have removed your meatball day from the database. :boom:")
         .await?;
 
@@ -208,3 +793,327 @@ pub async fn role(
 
     Ok(())
 }
+
+#[allow(clippy::unused_async)]
+async fn autocomplete_timezone<'a>(
+    _ctx: CommandContext<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = &'static str> + 'a {
+    let partial = partial.to_lowercase();
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|timezone| timezone.name())
+        .filter(move |name| name.to_lowercase().contains(&partial))
+        .take(25)
+}
+
+/// Set the timezone that meatball days start and end in.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn timezone(
+    ctx: CommandContext<'_>,
+    #[description = "The timezone to use, e.g. Europe/London"]
+    #[autocomplete = "autocomplete_timezone"]
+    timezone: String,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let timezone: chrono_tz::Tz = match timezone.parse() {
+        Ok(timezone) => timezone,
+        Err(_) => {
+            ctx.say("I don't know that timezone... :thinking:").await?;
+            return Ok(());
+        }
+    };
+
+    sqlx::query(include_str!("queries/meatball-timezone.sql"))
+        .bind(guild.id.to_string())
+        .bind(timezone.name())
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().meatball_wake.notify_one();
+
+    ctx.say(format!("I have set the meatball timezone to {timezone}"))
+        .await?;
+
+    Ok(())
+}
+
+/// Set the hour of the day that meatball days are announced at.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn announcehour(
+    ctx: CommandContext<'_>,
+    #[description = "The hour to announce at in the meatball timezone, from 0 to 23"]
+    #[min = 0]
+    #[max = 23]
+    hour: u8,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    if hour > 23 {
+        ctx.say("That's not an hour of the day... :thinking:")
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(include_str!("queries/meatball-announcehour.sql"))
+        .bind(guild.id.to_string())
+        .bind(hour)
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().meatball_wake.notify_one();
+
+    ctx.say(format!(
+        "Got it, I will announce meatball days at {hour:02}:00. \
+        The role is still given out at midnight."
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set how numeric dates like 03/04 are read.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn dateorder(
+    ctx: CommandContext<'_>,
+    #[description = "Whether the day or the month comes first"] order: DateOrder,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    sqlx::query(include_str!("queries/meatball-dateorder.sql"))
+        .bind(guild.id.to_string())
+        .bind(order.to_setting())
+        .execute(&ctx.data().db)
+        .await?;
+
+    let example = match order {
+        DateOrder::DayFirst => "03/04 is the 3rd of April",
+        DateOrder::MonthFirst => "03/04 is March 4th",
+    };
+    ctx.say(format!("Got it, {example}.")).await?;
+
+    Ok(())
+}
+
+/// Set how dates are laid out. Month names are always in English.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn dateformat(
+    ctx: CommandContext<'_>,
+    #[description = "The date layout to use, month names are in English"] format: DateFormat,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    sqlx::query(include_str!("queries/meatball-dateformat.sql"))
+        .bind(guild.id.to_string())
+        .bind(format.to_setting())
+        .execute(&ctx.data().db)
+        .await?;
+
+    ctx.say(format!(
+        "Got it, dates will look like {}.",
+        dates::format_day_month(format, 3, 3)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Show upcoming meatball days in the server's events tab.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn events(
+    ctx: CommandContext<'_>,
+    #[description = "Whether to create events for meatball days"] enabled: bool,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    sqlx::query(include_str!("queries/meatball-events.sql"))
+        .bind(guild.id.to_string())
+        .bind(enabled)
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().meatball_wake.notify_one();
+
+    if enabled {
+        ctx.say("I will add upcoming meatball days to the server's events. :calendar:")
+            .await?;
+    } else {
+        ctx.say("I will remove meatball days from the server's events.")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// How far back to look for meatball days that didn't get celebrated.
+const AUDIT_MISSED_DAYS: i64 = 30;
+
+/// How many lines to show on each page of the audit, keeping a page under
+/// discord's message limit.
+const AUDIT_PAGE_SIZE: usize = 15;
+
+/// User, date, grant time, revoke time, announcement message and outcome.
+type AuditRow = (
+    String,
+    chrono::NaiveDate,
+    Option<chrono::DateTime<chrono::Utc>>,
+    Option<chrono::DateTime<chrono::Utc>>,
+    Option<String>,
+    String,
+);
+
+/// Show recent celebrations and any meatball days that were missed.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR", ephemeral)]
+pub async fn audit(ctx: CommandContext<'_>) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let db = &ctx.data().db;
+
+    let format = settings::get_date_format(guild.id, db).await?;
+    let timezone = settings::get_timezone(guild.id, db).await?;
+    let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+    let since = today - chrono::Duration::days(AUDIT_MISSED_DAYS);
+
+    let recent: Vec<AuditRow> = sqlx::query_as(include_str!("queries/meatball-audit.sql"))
+        .bind(guild.id.to_string())
+        .fetch_all(db)
+        .await?;
+
+    let mut lines = vec!["**Recent celebrations**".to_owned()];
+    if recent.is_empty() {
+        lines.push("None yet!".to_owned());
+    }
+    let timestamp = |time: Option<chrono::DateTime<chrono::Utc>>| {
+        time.map_or_else(
+            || "unknown".to_owned(),
+            |time| format!("<t:{}:f>", time.timestamp()),
+        )
+    };
+    for (user, date, granted_at, revoked_at, message, outcome) in recent {
+        lines.push(format!(
+            "- {} on {}: {outcome}, granted {}, revoked {}{}",
+            serenity::Mention::from(serenity::UserId(user.parse()?)),
+            dates::format_date(format, date),
+            timestamp(granted_at),
+            timestamp(revoked_at),
+            if message.is_some() { ", announced" } else { "" },
+        ));
+    }
+
+    // members who opted out of both the announcement and the role are never
+    // celebrated, so they can't be missed.
+    let days: Vec<(String, u32, u32)> =
+        sqlx::query_as(include_str!("queries/meatball-audit-days.sql"))
+            .bind(guild.id.to_string())
+            .fetch_all(db)
+            .await?;
+
+    let celebrated: Vec<(String, chrono::NaiveDate)> =
+        sqlx::query_as(include_str!("queries/meatball-audit-dates.sql"))
+            .bind(guild.id.to_string())
+            .bind(since)
+            .fetch_all(db)
+            .await?;
+
+    let missed = days
+        .into_iter()
+        .filter_map(|(user, month, day)| {
+            let date = dates::previous_occurrence(month, day, today)?;
+            let was_celebrated = celebrated.contains(&(user.clone(), date));
+            (date >= since && !was_celebrated).then_some((user, date))
+        })
+        .sorted_by_key(|(_, date)| *date)
+        .collect_vec();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Possibly missed in the last {AUDIT_MISSED_DAYS} days**"
+    ));
+    lines.push("_Members who saved their day after it passed show up here too._".to_owned());
+    if missed.is_empty() {
+        lines.push("Nobody, nice!".to_owned());
+    }
+    for (user, date) in missed {
+        lines.push(format!(
+            "- {} on {}",
+            serenity::Mention::from(serenity::UserId(user.parse()?)),
+            dates::format_date(format, date),
+        ));
+    }
+
+    let conflicts: Vec<(String, u32, u32, u32, u32)> =
+        sqlx::query_as(include_str!("queries/meatball-audit-conflicts.sql"))
+            .bind(guild.id.to_string())
+            .fetch_all(db)
+            .await?;
+
+    if !conflicts.is_empty() {
+        lines.push(String::new());
+        lines.push("**Replaced when days were moved to profiles**".to_owned());
+        lines.push("_These members had a different day saved in another server._".to_owned());
+    }
+    for (user, month, day, kept_month, kept_day) in conflicts {
+        lines.push(format!(
+            "- {}: {} became {}",
+            serenity::Mention::from(serenity::UserId(user.parse()?)),
+            dates::format_day_month(format, month, day),
+            dates::format_day_month(format, kept_month, kept_day),
+        ));
+    }
+
+    let pages = lines
+        .chunks(AUDIT_PAGE_SIZE)
+        .map(|chunk| chunk.join("\n"))
+        .collect_vec();
+    let pages = pages.iter().map(String::as_str).collect_vec();
+
+    poise::builtins::paginate(ctx, &pages).await?;
+
+    Ok(())
+}
+
+/// Give members a role for the whole month of their meatball day.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn monthrole(
+    ctx: CommandContext<'_>,
+    #[description = "The month to set the role for"]
+    #[autocomplete = "autocomplete_date"]
+    month: String,
+    #[description = "The role to give (leave empty to stop giving one)"] role: Option<
+        serenity::Role,
+    >,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let Some(month) = dates::parse_month(&month) else {
+        ctx.say("That's not a real month... :thinking:").await?;
+        return Ok(());
+    };
+
+    if let Some(role) = role {
+        sqlx::query(include_str!("queries/meatball-monthrole.sql"))
+            .bind(guild.id.to_string())
+            .bind(month)
+            .bind(role.id.to_string())
+            .execute(&ctx.data().db)
+            .await?;
+
+        ctx.say(format!(
+            "I will give members born in {} the {} role",
+            dates::month_name(month),
+            serenity::Mention::from(role.id)
+        ))
+        .await?;
+    } else {
+        sqlx::query(include_str!("queries/meatball-monthrole-clear.sql"))
+            .bind(guild.id.to_string())
+            .bind(month)
+            .execute(&ctx.data().db)
+            .await?;
+
+        ctx.say(format!(
+            "I will stop giving members born in {} a role",
+            dates::month_name(month)
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,435 @@
+use chrono::{Datelike, NaiveDate};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Month names are only read and written in English. Guilds can choose the
+/// order dates are read in and the layout they're shown in, but not the
+/// language.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+fn regex(pattern: &str) -> Regex {
+    #[allow(clippy::expect_used)] // patterns are constant
+    Regex::new(pattern).expect("failed to compile regex")
+}
+
+lazy_static! {
+    static ref ISO_DATE: Regex = regex(r"^(\d{4})-(\d{1,2})-(\d{1,2})$");
+    static ref NUMERIC_DATE: Regex = regex(r"^(\d{1,2})[/.-](\d{1,2})(?:[/.-](\d{4}))?$");
+}
+
+/// Which way round to read ambiguous dates like 03/04.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum DateOrder {
+    #[name = "Day first (DD/MM)"]
+    DayFirst,
+    #[default]
+    #[name = "Month first (MM/DD)"]
+    MonthFirst,
+}
+
+impl DateOrder {
+    pub const fn to_setting(self) -> &'static str {
+        match self {
+            Self::DayFirst => "day_first",
+            Self::MonthFirst => "month_first",
+        }
+    }
+
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        match setting {
+            "day_first" => Some(Self::DayFirst),
+            "month_first" => Some(Self::MonthFirst),
+            _ => None,
+        }
+    }
+}
+
+/// A date as the user wrote it. The month and day are not validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedDate {
+    pub year: Option<i32>,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl ParsedDate {
+    /// Whether the month and day make a real day of the year. February 29th
+    /// counts, since it comes around in leap years.
+    pub fn is_real(&self) -> bool {
+        (1..=12).contains(&self.month) && (1..=days_in_month(self.month)).contains(&self.day)
+    }
+}
+
+/// The most days the month can have, counting February 29th.
+const fn days_in_month(month: u32) -> u32 {
+    [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31][month as usize - 1]
+}
+
+/// Parses dates like "March 3rd", "3 Mar", "03/03" and "2000-03-03".
+/// Purely numeric dates are read using the given `order`.
+pub fn parse_date(input: &str, order: DateOrder) -> Option<ParsedDate> {
+    let input = input.trim().to_lowercase();
+
+    if let Some(captures) = ISO_DATE.captures(&input) {
+        return Some(ParsedDate {
+            year: Some(captures[1].parse().ok()?),
+            month: captures[2].parse().ok()?,
+            day: captures[3].parse().ok()?,
+        });
+    }
+
+    if let Some(captures) = NUMERIC_DATE.captures(&input) {
+        let first = captures[1].parse().ok()?;
+        let second = captures[2].parse().ok()?;
+        let (month, day) = match order {
+            DateOrder::DayFirst => (second, first),
+            DateOrder::MonthFirst => (first, second),
+        };
+        return Some(ParsedDate {
+            year: captures.get(3).and_then(|year| year.as_str().parse().ok()),
+            month,
+            day,
+        });
+    }
+
+    parse_written_date(&input)
+}
+
+/// Parses dates with the month written out, in any order: "3rd of March", "Mar 3, 2000".
+fn parse_written_date(input: &str) -> Option<ParsedDate> {
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+
+    let tokens = input
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '.')
+        .filter(|token| !token.is_empty() && !matches!(*token, "of" | "the"));
+
+    for token in tokens {
+        // each part may only appear once
+        let duplicate = if let Some(parsed) = parse_month_name(token) {
+            month.replace(parsed).is_some()
+        } else if token.len() == 4 && token.chars().all(|c| c.is_ascii_digit()) {
+            year.replace(token.parse().ok()?).is_some()
+        } else {
+            day.replace(parse_day(token)?).is_some()
+        };
+
+        if duplicate {
+            return None;
+        }
+    }
+
+    Some(ParsedDate {
+        year,
+        month: month?,
+        day: day?,
+    })
+}
+
+/// Parses a month given by name or number, e.g. "March", "mar" or "3".
+pub fn parse_month(input: &str) -> Option<u32> {
+    let input = input.trim().to_lowercase();
+    match input.parse() {
+        Ok(month) if (1..=12).contains(&month) => Some(month),
+        Ok(_) => None,
+        Err(_) => parse_month_name(&input),
+    }
+}
+
+/// Matches full or abbreviated month names, e.g. "mar" or "sept".
+fn parse_month_name(token: &str) -> Option<u32> {
+    if token.len() < 3 {
+        return None;
+    }
+
+    MONTHS
+        .iter()
+        .position(|name| name.to_lowercase().starts_with(token))
+        .and_then(|index| u32::try_from(index + 1).ok())
+}
+
+/// Matches day numbers with an optional ordinal suffix, e.g. "3" or "3rd".
+/// The suffix has to fit the number, so "3th" isn't a day.
+fn parse_day(token: &str) -> Option<u32> {
+    let (digits, suffix) = token.split_at(
+        token
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(token.len()),
+    );
+
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+
+    let day = digits.parse().ok()?;
+    (suffix.is_empty() || suffix == ordinal_suffix(day)).then_some(day)
+}
+
+/// Suggests month names for the last word of a partially typed date.
+pub fn complete_month_names(partial: &str) -> Vec<String> {
+    let (head, last) = partial
+        .rsplit_once(' ')
+        .map_or(("", partial), |(head, last)| (head, last));
+
+    if !last.chars().all(char::is_alphabetic) {
+        return vec![partial.to_owned()];
+    }
+
+    let last = last.to_lowercase();
+    MONTHS
+        .iter()
+        .filter(|name| name.to_lowercase().starts_with(&last))
+        .map(|name| {
+            if head.is_empty() {
+                (*name).to_owned()
+            } else {
+                format!("{head} {name}")
+            }
+        })
+        .collect()
+}
+
+/// How dates are laid out for a guild. Month names are always in English.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum DateFormat {
+    #[name = "3 March"]
+    DayMonth,
+    #[default]
+    #[name = "March 3rd"]
+    MonthDay,
+    #[name = "ISO (03-03)"]
+    Iso,
+}
+
+impl DateFormat {
+    pub const fn to_setting(self) -> &'static str {
+        match self {
+            Self::DayMonth => "day_month",
+            Self::MonthDay => "month_day",
+            Self::Iso => "iso",
+        }
+    }
+
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        match setting {
+            "day_month" => Some(Self::DayMonth),
+            "month_day" => Some(Self::MonthDay),
+            "iso" => Some(Self::Iso),
+            _ => None,
+        }
+    }
+}
+
+/// The month's full name, e.g. "March".
+pub fn month_name(month: u32) -> &'static str {
+    month
+        .checked_sub(1)
+        .and_then(|index| MONTHS.get(index as usize))
+        .unwrap_or(&"???")
+}
+
+/// The first three letters of the month's name, e.g. "Mar".
+pub fn short_month_name(month: u32) -> &'static str {
+    &month_name(month)[..3]
+}
+
+/// Formats a day of the year, e.g. "March 3rd".
+pub fn format_day_month(format: DateFormat, month: u32, day: u32) -> String {
+    let name = month_name(month);
+
+    match format {
+        DateFormat::DayMonth => format!("{day} {name}"),
+        DateFormat::MonthDay => format!("{name} {}", ordinal(day)),
+        DateFormat::Iso => format!("{month:02}-{day:02}"),
+    }
+}
+
+/// Formats a full date, e.g. "March 3rd, 2023".
+pub fn format_date(format: DateFormat, date: NaiveDate) -> String {
+    match format {
+        DateFormat::DayMonth => format!(
+            "{} {}",
+            format_day_month(format, date.month(), date.day()),
+            date.year()
+        ),
+        DateFormat::MonthDay => format!(
+            "{}, {}",
+            format_day_month(format, date.month(), date.day()),
+            date.year()
+        ),
+        DateFormat::Iso => date.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// 1st, 2nd, 3rd, 4th, ..., 11th, 12th, 13th, ..., 21st, ...
+fn ordinal(n: u32) -> String {
+    format!("{n}{}", ordinal_suffix(n))
+}
+
+const fn ordinal_suffix(n: u32) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// The first time the given day of the year comes around after `after`.
+/// February 29th only comes around in leap years.
+pub fn next_occurrence(month: u32, day: u32, after: NaiveDate) -> Option<NaiveDate> {
+    (after.year()..=after.year() + 4)
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .find(|date| *date > after)
+}
+
+/// The last time the given day of the year came around before `before`.
+pub fn previous_occurrence(month: u32, day: u32, before: NaiveDate) -> Option<NaiveDate> {
+    (before.year() - 4..=before.year())
+        .rev()
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .find(|date| *date < before)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const fn date(year: Option<i32>, month: u32, day: u32) -> ParsedDate {
+        ParsedDate { year, month, day }
+    }
+
+    #[test]
+    fn numeric_dates_follow_the_order() {
+        assert_eq!(
+            parse_date("03/04", DateOrder::DayFirst),
+            Some(date(None, 4, 3))
+        );
+        assert_eq!(
+            parse_date("03/04", DateOrder::MonthFirst),
+            Some(date(None, 3, 4))
+        );
+        assert_eq!(
+            parse_date("3.4.2000", DateOrder::DayFirst),
+            Some(date(Some(2000), 4, 3))
+        );
+        assert_eq!(
+            parse_date("3-4-2000", DateOrder::MonthFirst),
+            Some(date(Some(2000), 3, 4))
+        );
+    }
+
+    #[test]
+    fn iso_dates_ignore_the_order() {
+        for order in [DateOrder::DayFirst, DateOrder::MonthFirst] {
+            assert_eq!(
+                parse_date("2000-03-04", order),
+                Some(date(Some(2000), 3, 4))
+            );
+        }
+    }
+
+    #[test]
+    fn written_months() {
+        let order = DateOrder::default();
+        assert_eq!(parse_date("March 3rd", order), Some(date(None, 3, 3)));
+        assert_eq!(parse_date("3 mar", order), Some(date(None, 3, 3)));
+        assert_eq!(
+            parse_date("the 21st of September", order),
+            Some(date(None, 9, 21))
+        );
+        assert_eq!(
+            parse_date("Sept 2nd, 2000", order),
+            Some(date(Some(2000), 9, 2))
+        );
+        assert_eq!(parse_date("  DEC 31  ", order), Some(date(None, 12, 31)));
+    }
+
+    #[test]
+    fn nonsense_is_rejected() {
+        let order = DateOrder::default();
+        assert_eq!(parse_date("", order), None);
+        assert_eq!(parse_date("march", order), None);
+        assert_eq!(parse_date("3rd", order), None);
+        assert_eq!(parse_date("march april 3", order), None);
+        assert_eq!(parse_date("3 4 march", order), None);
+        assert_eq!(parse_date("ma 3", order), None);
+        assert_eq!(parse_date("123 march", order), None);
+    }
+
+    #[test]
+    fn ordinals_must_fit_the_day() {
+        let order = DateOrder::default();
+        for day in [
+            "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "22nd", "23rd",
+        ] {
+            assert!(
+                parse_date(&format!("{day} March"), order).is_some(),
+                "{day}"
+            );
+        }
+        for day in ["3th", "1nd", "22rd", "11st", "12nd", "2th", "3rdd"] {
+            assert_eq!(parse_date(&format!("{day} March"), order), None, "{day}");
+        }
+    }
+
+    #[test]
+    fn invalid_days_are_not_real() {
+        let order = DateOrder::default();
+        let april = parse_date("31 April", order).unwrap();
+        assert_eq!(april, date(None, 4, 31));
+        assert!(!april.is_real());
+
+        assert!(!parse_date("13/01", DateOrder::MonthFirst)
+            .unwrap()
+            .is_real());
+        assert!(!parse_date("0 march", order).unwrap().is_real());
+        assert!(parse_date("30 April", order).unwrap().is_real());
+    }
+
+    #[test]
+    fn february_29th_is_real() {
+        let leap = parse_date("29/02", DateOrder::DayFirst).unwrap();
+        assert_eq!(leap, date(None, 2, 29));
+        assert!(leap.is_real());
+        assert!(!parse_date("Feb 30", DateOrder::default())
+            .unwrap()
+            .is_real());
+    }
+
+    #[test]
+    fn february_29th_comes_around_in_leap_years() {
+        let after = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+        assert_eq!(
+            next_occurrence(2, 29, after),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+    }
+
+    #[test]
+    fn month_names_complete_the_last_word() {
+        assert_eq!(complete_month_names("ju"), ["June", "July"]);
+        assert_eq!(
+            complete_month_names("3rd of ma"),
+            ["3rd of March", "3rd of May"]
+        );
+        assert_eq!(complete_month_names("03/03"), ["03/03"]);
+        assert!(complete_month_names("xyz").is_empty());
+    }
+}
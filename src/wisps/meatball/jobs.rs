@@ -1,27 +1,264 @@
-use chrono::Datelike;
-use color_eyre::{eyre::eyre, Result};
+use std::collections::HashSet;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use color_eyre::{
+    eyre::{eyre, ErrReport},
+    Result,
+};
+use itertools::Itertools;
 use poise::serenity_prelude as serenity;
-use sqlx::SqlitePool;
-use tracing::info;
+use sqlx::{SqliteExecutor, SqlitePool};
+use tracing::{error, info, warn};
 
+use super::{dates, scheduled_events, settings};
 use crate::bot::JobContext;
 
 pub async fn update_role_assignments(ctx: JobContext) -> Result<()> {
+    let _lock = ctx.assignment_lock.lock().await;
     remove_expired_assignments(&ctx).await?;
     add_pending_assignments(&ctx).await?;
+    announce_due_celebrations(&ctx).await?;
+
+    if let Err(e) = scheduled_events::sync_scheduled_events(&ctx).await {
+        error!("Failed to sync meatball day events: {e}");
+    }
+
     Ok(())
 }
 
-async fn get_guild_role(guild: serenity::GuildId, pool: &SqlitePool) -> Result<serenity::RoleId> {
-    let result: (String,) = sqlx::query_as(include_str!("queries/get_guild_meatball_role.sql"))
-        .bind(guild.to_string())
+/// Migration 0006 merges the days members saved in each guild into a single
+/// profile. Run before migrating, this keeps the days it is about to drop so
+/// `/meatball audit` can show them.
+pub async fn save_profile_conflicts(pool: &SqlitePool) -> Result<()> {
+    // once 0006 has run, meatball_day is a view over the profiles.
+    let (pending,): (bool,) = sqlx::query_as(include_str!("queries/get_meatball_day_is_table.sql"))
         .fetch_one(pool)
-        .await
-        .map_err(|e| {
-            eyre!("failed to get role for guild {guild}. it might not be set yet! ({e})")
-        })?;
+        .await?;
+
+    if pending {
+        info!("Saving meatball days that the profile migration will replace");
+        sqlx::query(include_str!("queries/save_meatball_profile_conflicts.sql"))
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Celebrates a single member straight away if today is their meatball day
+/// and they haven't been celebrated yet. Returns whether they were.
+pub async fn celebrate_member(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<bool> {
+    let _lock = ctx.assignment_lock.lock().await;
+
+    let rows = sqlx::query_as(include_str!("queries/get_pending_meatball_assignment.sql"))
+        .bind(guild.to_string())
+        .bind(user.to_string())
+        .fetch_all(&ctx.db)
+        .await?;
+
+    let Some((guild, date, assignment)) = pending_from_rows(rows)?.pop() else {
+        return Ok(false);
+    };
+
+    let celebrated = add_pending_assignments_for_guild(guild, date, &[assignment], ctx).await?;
+    if celebrated > 0 {
+        let due = get_due_announcements(&ctx.db)
+            .await?
+            .into_iter()
+            .filter(|(due_guild, _, _)| *due_guild == guild)
+            .collect_vec();
+        announce_due(due, ctx).await;
+    }
+    Ok(celebrated > 0)
+}
+
+/// Takes the role away from a member straight away if they currently have it.
+pub async fn revoke_member(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<()> {
+    let _lock = ctx.assignment_lock.lock().await;
+
+    let assignment: Option<(Option<String>,)> =
+        sqlx::query_as(include_str!("queries/get_meatball_assignment.sql"))
+            .bind(guild.to_string())
+            .bind(user.to_string())
+            .fetch_optional(&ctx.db)
+            .await?;
+
+    if let Some((role,)) = assignment {
+        let role = parse_role(role.as_deref())?;
+        remove_assignment(guild, user, role, Outcome::Forgotten, ctx).await?;
+    }
+
+    Ok(())
+}
+
+/// Takes the role back from a member who opted out of it during their day.
+/// The celebration itself carries on.
+pub async fn withdraw_role(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<()> {
+    let _lock = ctx.assignment_lock.lock().await;
+
+    let assignment: Option<(Option<String>,)> =
+        sqlx::query_as(include_str!("queries/get_meatball_assignment.sql"))
+            .bind(guild.to_string())
+            .bind(user.to_string())
+            .fetch_optional(&ctx.db)
+            .await?;
+
+    let Some(role) = parse_role(assignment.and_then(|(role,)| role).as_deref())? else {
+        return Ok(());
+    };
+
+    match remove_role(guild, user, role, ctx).await {
+        Ok(()) => {}
+        Err(e) if is_unknown_member(&e) => {
+            info!("User '{user}' has left guild {guild}, forgetting their role.");
+        }
+        Err(e) => return Err(e),
+    }
+
+    sqlx::query(include_str!("queries/clear_meatball_assignment_role.sql"))
+        .bind(guild.to_string())
+        .bind(user.to_string())
+        .execute(&ctx.db)
+        .await?;
+
+    Ok(())
+}
+
+/// Runs `update_role_assignments` whenever a meatball day could start or end,
+/// or when woken up by a change to the saved meatball days.
+pub async fn run_assignment_scheduler(ctx: JobContext) {
+    loop {
+        if let Err(e) = update_role_assignments(ctx.clone()).await {
+            error!("Failed to update meatball role assignments: {e}");
+        }
+
+        let now = Utc::now();
+        let sleep = match next_boundary(&ctx.db, now).await {
+            Ok(Some(next)) => {
+                info!("Next meatball boundary is at {next}");
+                (next - now).to_std().unwrap_or_default()
+            }
+            Ok(None) => {
+                info!("No upcoming meatball boundaries, waiting for changes");
+                std::time::Duration::MAX
+            }
+            Err(e) => {
+                // the reconciliation job will pick up the slack until we're
+                // woken up again.
+                error!("Failed to compute next meatball boundary: {e}");
+                std::time::Duration::MAX
+            }
+        };
+
+        tokio::select! {
+            () = tokio::time::sleep(sleep) => {}
+            () = ctx.meatball_wake.notified() => {
+                info!("Meatball days changed, re-running assignments");
+            }
+        }
+    }
+}
+
+/// The next time a meatball day starts or ends in any guild we have data for,
+/// or a celebration is due to be announced. Days start and end at midnight in
+/// the guild's timezone.
+async fn next_boundary(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    let rows: Vec<(String,)> = sqlx::query_as(include_str!("queries/get_meatball_timezones.sql"))
+        .fetch_all(pool)
+        .await?;
+
+    let midnights = rows
+        .into_iter()
+        .filter_map(|(timezone,)| next_midnight(parse_timezone(&timezone), now));
+
+    let announcements: Vec<AnnouncementRow> = sqlx::query_as(include_str!(
+        "queries/get_pending_meatball_announcements.sql"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let announcements = announcements
+        .into_iter()
+        .filter_map(|(_, _, date, timezone, hour)| {
+            local_to_utc(parse_timezone(&timezone), date.and_hms_opt(hour, 0, 0)?)
+        })
+        .filter(|&time| time > now);
+
+    Ok(midnights.chain(announcements).min())
+}
 
-    Ok(serenity::RoleId(result.0.parse()?))
+fn next_midnight(timezone: Tz, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let tomorrow = now.with_timezone(&timezone).date_naive().succ_opt()?;
+    local_to_utc(timezone, tomorrow.and_hms_opt(0, 0, 0)?)
+}
+
+/// Converts a local time to UTC. A time that a daylight saving change skips
+/// over becomes the first moment after the gap, so it isn't missed.
+fn local_to_utc(timezone: Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    // gaps are normally an hour, but a timezone has skipped a whole day before.
+    (0..=24 * 60)
+        .filter_map(|minutes| local.checked_add_signed(Duration::minutes(minutes)))
+        .find_map(|local| timezone.from_local_datetime(&local).earliest())
+        .map(|time| time.with_timezone(&Utc))
+}
+
+pub(crate) fn parse_timezone(timezone: &str) -> Tz {
+    timezone.parse().unwrap_or_else(|e| {
+        warn!("Falling back to UTC for invalid timezone {timezone}: {e}");
+        Tz::UTC
+    })
+}
+
+fn local_today(timezone: &str) -> NaiveDate {
+    Utc::now()
+        .with_timezone(&parse_timezone(timezone))
+        .date_naive()
+}
+
+async fn get_guild_role(
+    guild: serenity::GuildId,
+    pool: &SqlitePool,
+) -> Result<Option<serenity::RoleId>> {
+    let result: Option<(String,)> =
+        sqlx::query_as(include_str!("queries/get_guild_meatball_role.sql"))
+            .bind(guild.to_string())
+            .fetch_optional(pool)
+            .await?;
+
+    parse_role(result.as_ref().map(|(role,)| role.as_str()))
+}
+
+fn parse_role(role: Option<&str>) -> Result<Option<serenity::RoleId>> {
+    Ok(role
+        .map(str::parse::<u64>)
+        .transpose()?
+        .map(serenity::RoleId))
+}
+
+/// Whether Discord told us the member isn't in the guild any more.
+pub(crate) fn is_unknown_member(e: &ErrReport) -> bool {
+    const UNKNOWN_MEMBER: isize = 10007;
+
+    let Some(serenity::SerenityError::Http(e)) = e.downcast_ref() else {
+        return false;
+    };
+    matches!(
+        e.as_ref(),
+        serenity::HttpError::UnsuccessfulRequest(response) if response.error.code == UNKNOWN_MEMBER
+    )
 }
 
 async fn get_guild_channel(
@@ -39,26 +276,196 @@ async fn get_guild_channel(
     Ok(serenity::ChannelId(result.0.parse()?))
 }
 
+/// A member whose meatball day has started, and what they've opted in to.
+struct PendingAssignment {
+    user: serenity::UserId,
+    announce: bool,
+    role: bool,
+}
+
 async fn add_pending_assignments(ctx: &JobContext) -> Result<()> {
     let pending = get_pending_assignments(&ctx.db).await?;
 
-    for (guild, user) in pending {
-        add_pending_assignment(guild, user, ctx).await?;
+    let by_guild = pending
+        .into_iter()
+        .map(|(guild, date, assignment)| ((guild, date), assignment))
+        .into_group_map();
+
+    for ((guild, date), assignments) in by_guild {
+        if let Err(e) = add_pending_assignments_for_guild(guild, date, &assignments, ctx).await {
+            error!("Failed to add pending assignments for guild {guild}: {e}");
+        }
     }
 
     Ok(())
 }
 
-async fn add_pending_assignment(
+async fn add_pending_assignments_for_guild(
     guild: serenity::GuildId,
-    user: serenity::UserId,
+    date: NaiveDate,
+    assignments: &[PendingAssignment],
     ctx: &JobContext,
-) -> Result<()> {
-    // if we fail to add the assignment, we can roll back the transaction
+) -> Result<usize> {
+    let role = if assignments.iter().any(|assignment| assignment.role) {
+        let role = get_guild_role(guild, &ctx.db).await?;
+        if role.is_none() {
+            warn!("Guild {guild} has no meatball role set, celebrating without one");
+        }
+        role
+    } else {
+        None
+    };
+
+    // members we fail to give the role to aren't recorded, so they get picked
+    // up again on the next run.
+    let mut celebrated = vec![];
+    for assignment in assignments {
+        let user = assignment.user;
+        let granted = match (assignment.role, role) {
+            (true, Some(role)) => {
+                if let Err(e) = add_role(guild, user, role, ctx).await {
+                    error!("Failed to add role '{role}' to user '{user}': {e}");
+                    continue;
+                }
+                Some(role)
+            }
+            _ => None,
+        };
+        celebrated.push((assignment, granted));
+    }
+
+    // if we fail to record the assignments, we can roll back the transaction
     // and try again later.
-    let tx = ctx.db.begin().await?;
+    info!("Roles added successfully, adding role assignments to DB");
+    let mut tx = ctx.db.begin().await?;
+    for (assignment, granted) in &celebrated {
+        create_assignment(
+            guild,
+            assignment.user,
+            date,
+            assignment.announce,
+            *granted,
+            &mut tx,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(celebrated.len())
+}
+
+/// Announces celebrations once the guild's announcement hour has come around
+/// on the day itself.
+async fn announce_due_celebrations(ctx: &JobContext) -> Result<()> {
+    let due = get_due_announcements(&ctx.db).await?;
+    announce_due(due, ctx).await;
+    Ok(())
+}
+
+async fn announce_due(due: Vec<DueAnnouncement>, ctx: &JobContext) {
+    let by_guild = due
+        .into_iter()
+        .map(|(guild, date, user)| ((guild, date), user))
+        .into_group_map();
+
+    for ((guild, date), users) in by_guild {
+        if let Err(e) = announce_celebrations(guild, date, &users, ctx).await {
+            error!("Failed to announce meatball days for guild {guild}: {e}");
+        }
+    }
+}
+
+async fn announce_celebrations(
+    guild: serenity::GuildId,
+    date: NaiveDate,
+    users: &[serenity::UserId],
+    ctx: &JobContext,
+) -> Result<()> {
+    let message = announce(guild, date, users, ctx).await?;
+
+    let mut tx = ctx.db.begin().await?;
+    for &user in users {
+        sqlx::query(include_str!("queries/announce_meatball_celebration.sql"))
+            .bind(message.to_string())
+            .bind(guild.to_string())
+            .bind(user.to_string())
+            .bind(date)
+            .execute(&mut tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+type AnnouncementRow = (String, String, NaiveDate, String, u32);
+type DueAnnouncement = (serenity::GuildId, NaiveDate, serenity::UserId);
+
+/// Celebrations still waiting to be announced whose announcement hour has
+/// passed today in the guild's timezone.
+async fn get_due_announcements(pool: &SqlitePool) -> Result<Vec<DueAnnouncement>> {
+    let rows: Vec<AnnouncementRow> = sqlx::query_as(include_str!(
+        "queries/get_pending_meatball_announcements.sql"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let now = Utc::now();
+    let mut due = vec![];
+    for (guild, user, date, timezone, hour) in rows {
+        let local = now.with_timezone(&parse_timezone(&timezone));
+        if local.date_naive() == date && local.hour() >= hour {
+            due.push((
+                serenity::GuildId(guild.parse()?),
+                date,
+                serenity::UserId(user.parse()?),
+            ));
+        }
+    }
+
+    Ok(due)
+}
+
+async fn announce(
+    guild: serenity::GuildId,
+    date: NaiveDate,
+    users: &[serenity::UserId],
+    ctx: &JobContext,
+) -> Result<serenity::MessageId> {
+    let channel = get_guild_channel(guild, &ctx.db).await?;
+    let format = settings::get_date_format(guild, &ctx.db).await?;
+    info!(
+        "Notifying channel '{}'",
+        channel
+            .name(&ctx.ctx.cache)
+            .await
+            .unwrap_or_else(|| channel.to_string())
+    );
+    let message = channel
+        .send_message(&ctx.ctx.http, |message| {
+            message.content(format!(
+                "It's {}, which means it's {}'s meatball day! :partying_face::tada:",
+                dates::format_day_month(format, date.month(), date.day()),
+                join_mentions(users)
+            ))
+        })
+        .await?;
+
+    for &user in users {
+        if let Err(e) = send_reminders(guild, user, ctx).await {
+            error!("Failed to send meatball day reminders for user '{user}': {e}");
+        }
+    }
+
+    Ok(message.id)
+}
 
-    let role = get_guild_role(guild, &ctx.db).await?;
+async fn add_role(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    role: serenity::RoleId,
+    ctx: &JobContext,
+) -> Result<()> {
     let mut member = guild.member(&ctx.ctx.http, user).await?;
 
     info!(
@@ -71,48 +478,134 @@ async fn add_pending_assignment(
     );
     member.add_role(&ctx.ctx.http, role).await?;
 
-    let channel = get_guild_channel(guild, &ctx.db).await?;
+    Ok(())
+}
+
+async fn remove_role(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    role: serenity::RoleId,
+    ctx: &JobContext,
+) -> Result<()> {
+    let mut member = guild.member(&ctx.ctx.http, user).await?;
+
     info!(
-        "Notifying channel '{}'",
-        channel
+        "Removing role '{}' from member '{}' of guild '{}'",
+        role,
+        member.display_name(),
+        guild
             .name(&ctx.ctx.cache)
-            .await
-            .unwrap_or_else(|| channel.to_string())
+            .unwrap_or_else(|| guild.to_string()),
     );
-    channel
+    member.remove_role(&ctx.ctx.http, role).await?;
+
+    Ok(())
+}
+
+/// DMs everyone who asked to be reminded about this user's meatball day.
+async fn send_reminders(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<()> {
+    let subscribers: Vec<(String,)> =
+        sqlx::query_as(include_str!("queries/get_meatball_reminders.sql"))
+            .bind(guild.to_string())
+            .bind(user.to_string())
+            .fetch_all(&ctx.db)
+            .await?;
+
+    let guild_name = guild
+        .name(&ctx.ctx.cache)
+        .unwrap_or_else(|| guild.to_string());
+
+    for (subscriber,) in subscribers {
+        let subscriber = serenity::UserId(subscriber.parse()?);
+        info!("Reminding user '{subscriber}' about user '{user}'");
+        // one subscriber with their DMs closed shouldn't stop the rest.
+        if let Err(e) = send_reminder(subscriber, user, &guild_name, ctx).await {
+            warn!("Failed to remind user '{subscriber}' about user '{user}': {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn send_reminder(
+    subscriber: serenity::UserId,
+    user: serenity::UserId,
+    guild_name: &str,
+    ctx: &JobContext,
+) -> Result<()> {
+    subscriber
+        .create_dm_channel(&ctx.ctx.http)
+        .await?
         .send_message(&ctx.ctx.http, |message| {
             message.content(format!(
-                "It's {}'s meatball day! :partying_face::tada:",
+                "It's {}'s meatball day over in {guild_name}! :partying_face:\n\
+                Use `/meatball unremind` there to stop these reminders.",
                 serenity::Mention::from(user)
             ))
         })
         .await?;
 
-    info!("Role added successfully, adding role assignment to DB");
-    create_assignment(guild, user, &ctx.db).await?;
-
-    tx.commit().await?;
-
     Ok(())
 }
 
+/// Formats a list of users as "@a, @b and @c".
+fn join_mentions(users: &[serenity::UserId]) -> String {
+    let mentions = users
+        .iter()
+        .map(|&user| serenity::Mention::from(user).to_string())
+        .collect_vec();
+
+    match mentions.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Members whose meatball day is today in their guild's timezone, but who
+/// haven't been celebrated yet.
 async fn get_pending_assignments(
     pool: &SqlitePool,
-) -> Result<Vec<(serenity::GuildId, serenity::UserId)>> {
-    let today = chrono::Utc::now();
-    let rows: Vec<(String, String)> =
-        sqlx::query_as(include_str!("queries/get_pending_meatball_assignments.sql"))
-            .bind(today.month())
-            .bind(today.day())
-            .fetch_all(pool)
-            .await?;
+) -> Result<Vec<(serenity::GuildId, NaiveDate, PendingAssignment)>> {
+    let rows = sqlx::query_as(include_str!("queries/get_pending_meatball_assignments.sql"))
+        .fetch_all(pool)
+        .await?;
+
+    pending_from_rows(rows)
+}
 
+type PendingRow = (
+    String,
+    String,
+    u32,
+    u32,
+    bool,
+    bool,
+    String,
+    Option<NaiveDate>,
+);
+
+fn pending_from_rows(
+    rows: Vec<PendingRow>,
+) -> Result<Vec<(serenity::GuildId, NaiveDate, PendingAssignment)>> {
     let mut new = vec![];
-    for (guild, user) in rows {
-        new.push((
-            serenity::GuildId(guild.parse()?),
-            serenity::UserId(user.parse()?),
-        ));
+    for (guild, user, month, day, announce, role, timezone, last_celebrated) in rows {
+        let today = local_today(&timezone);
+        if today.month() == month && today.day() == day && last_celebrated != Some(today) {
+            new.push((
+                serenity::GuildId(guild.parse()?),
+                today,
+                PendingAssignment {
+                    user: serenity::UserId(user.parse()?),
+                    announce,
+                    role,
+                },
+            ));
+        }
     }
 
     Ok(new)
@@ -121,59 +614,81 @@ async fn get_pending_assignments(
 async fn remove_expired_assignments(ctx: &JobContext) -> Result<()> {
     let expired = get_expired_assignments(&ctx.db).await?;
 
-    for (guild, user) in expired {
-        remove_expired_assignment(guild, user, ctx).await?;
+    for (guild, user, role) in expired {
+        if let Err(e) = remove_assignment(guild, user, role, Outcome::Completed, ctx).await {
+            error!("Failed to end meatball day for user '{user}' in guild {guild}: {e}");
+        }
     }
 
     Ok(())
 }
 
-async fn remove_expired_assignment(
+/// How a celebration came to an end.
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    /// The day ended.
+    Completed,
+    /// The member forgot their meatball day before it ended.
+    Forgotten,
+}
+
+impl Outcome {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Forgotten => "forgotten",
+        }
+    }
+}
+
+/// Ends a celebration, taking away the role it gave if it gave one.
+async fn remove_assignment(
     guild: serenity::GuildId,
     user: serenity::UserId,
+    role: Option<serenity::RoleId>,
+    outcome: Outcome,
     ctx: &JobContext,
 ) -> Result<()> {
-    // if we fail to remove the assignment, we can roll back the transaction
-    // and try again later.
-    let tx = ctx.db.begin().await?;
-
-    let role = get_guild_role(guild, &ctx.db).await?;
-    let mut member = guild.member(&ctx.ctx.http, user).await?;
-
-    info!(
-        "Removing role '{}' from member '{}' of guild '{}'",
-        role,
-        member.display_name(),
-        guild
-            .name(&ctx.ctx.cache)
-            .unwrap_or_else(|| guild.to_string()),
-    );
-    member.remove_role(&ctx.ctx.http, role).await?;
-
-    info!("Role removed successfully, dropping DB record.");
-    drop_expired_assignment(guild, user, &ctx.db).await?;
+    // if we fail to remove the role, the celebration stays active and we can
+    // try again later. members who have left took the role with them.
+    if let Some(role) = role {
+        match remove_role(guild, user, role, ctx).await {
+            Ok(()) => info!("Role removed successfully, closing DB record."),
+            Err(e) if is_unknown_member(&e) => {
+                info!("User '{user}' has left guild {guild}, closing DB record.");
+            }
+            Err(e) => return Err(e),
+        }
+    }
 
-    tx.commit().await?;
+    drop_assignment(guild, user, outcome, &ctx.db).await?;
 
     Ok(())
 }
 
-async fn get_expired_assignments(
-    pool: &SqlitePool,
-) -> Result<Vec<(serenity::GuildId, serenity::UserId)>> {
-    let today = chrono::Utc::now();
-    let rows: Vec<(String, String)> =
+type ExpiredAssignment = (
+    serenity::GuildId,
+    serenity::UserId,
+    Option<serenity::RoleId>,
+);
+
+/// Role assignments made on a day that has since ended in the guild's
+/// timezone, with the role they gave.
+async fn get_expired_assignments(pool: &SqlitePool) -> Result<Vec<ExpiredAssignment>> {
+    let rows: Vec<(String, String, NaiveDate, Option<String>, String)> =
         sqlx::query_as(include_str!("queries/get_expired_meatball_assignments.sql"))
-            .bind(today)
             .fetch_all(pool)
             .await?;
 
     let mut expired = vec![];
-    for (guild, user) in rows {
-        expired.push((
-            serenity::GuildId(guild.parse()?),
-            serenity::UserId(user.parse()?),
-        ));
+    for (guild, user, date, role, timezone) in rows {
+        if date != local_today(&timezone) {
+            expired.push((
+                serenity::GuildId(guild.parse()?),
+                serenity::UserId(user.parse()?),
+                parse_role(role.as_deref())?,
+            ));
+        }
     }
 
     Ok(expired)
@@ -182,27 +697,149 @@ async fn get_expired_assignments(
 async fn create_assignment(
     guild: serenity::GuildId,
     user: serenity::UserId,
-    pool: &SqlitePool,
+    date: NaiveDate,
+    announce: bool,
+    role: Option<serenity::RoleId>,
+    executor: impl SqliteExecutor<'_>,
 ) -> Result<()> {
-    let now = chrono::Utc::now();
     sqlx::query(include_str!("queries/create_meatball_assignment.sql"))
         .bind(guild.to_string())
         .bind(user.to_string())
-        .bind(now)
-        .execute(pool)
+        .bind(date)
+        .bind(Utc::now())
+        .bind(announce)
+        .bind(role.map(|role| role.to_string()))
+        .execute(executor)
         .await?;
     Ok(())
 }
 
-async fn drop_expired_assignment(
+async fn drop_assignment(
     guild: serenity::GuildId,
     user: serenity::UserId,
+    outcome: Outcome,
     pool: &SqlitePool,
 ) -> Result<()> {
     sqlx::query(include_str!("queries/drop_meatball_assignment.sql"))
+        .bind(Utc::now())
+        .bind(outcome.as_str())
         .bind(guild.to_string())
         .bind(user.to_string())
         .execute(pool)
         .await?;
     Ok(())
 }
+
+type MonthRoleAssignment = (serenity::GuildId, serenity::UserId, serenity::RoleId);
+
+/// Gives members their guild's role for the current month, and takes away
+/// roles for months that have ended.
+pub async fn update_month_roles(ctx: JobContext) -> Result<()> {
+    let wanted = get_wanted_month_roles(&ctx.db).await?;
+    let current = get_month_role_assignments(&ctx.db).await?;
+
+    for &(guild, user, role) in current.difference(&wanted) {
+        if let Err(e) = revoke_month_role(guild, user, role, &ctx).await {
+            error!("Failed to remove month role '{role}' from user '{user}': {e}");
+        }
+    }
+
+    for &(guild, user, role) in wanted.difference(&current) {
+        if let Err(e) = grant_month_role(guild, user, role, &ctx).await {
+            error!("Failed to add month role '{role}' to user '{user}': {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn grant_month_role(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    role: serenity::RoleId,
+    ctx: &JobContext,
+) -> Result<()> {
+    // members who have left are recorded as if they got the role, so we
+    // don't keep trying every hour. it's dropped again when the month ends.
+    if let Err(e) = add_role(guild, user, role, ctx).await {
+        if !is_unknown_member(&e) {
+            return Err(e);
+        }
+        info!("User '{user}' has left guild {guild}, skipping their month role");
+    }
+
+    sqlx::query(include_str!(
+        "queries/create_meatball_month_role_assignment.sql"
+    ))
+    .bind(guild.to_string())
+    .bind(user.to_string())
+    .bind(role.to_string())
+    .execute(&ctx.db)
+    .await?;
+    Ok(())
+}
+
+async fn revoke_month_role(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    role: serenity::RoleId,
+    ctx: &JobContext,
+) -> Result<()> {
+    // members who have left took the role with them.
+    if let Err(e) = remove_role(guild, user, role, ctx).await {
+        if !is_unknown_member(&e) {
+            return Err(e);
+        }
+        info!("User '{user}' has left guild {guild}, forgetting their month role");
+    }
+
+    sqlx::query(include_str!(
+        "queries/drop_meatball_month_role_assignment.sql"
+    ))
+    .bind(guild.to_string())
+    .bind(user.to_string())
+    .bind(role.to_string())
+    .execute(&ctx.db)
+    .await?;
+    Ok(())
+}
+
+/// Month roles that members should have right now, in their guild's timezone.
+async fn get_wanted_month_roles(pool: &SqlitePool) -> Result<HashSet<MonthRoleAssignment>> {
+    let rows: Vec<(String, String, u32, String, String)> =
+        sqlx::query_as(include_str!("queries/get_wanted_meatball_month_roles.sql"))
+            .fetch_all(pool)
+            .await?;
+
+    let mut wanted = HashSet::new();
+    for (guild, user, month, role, timezone) in rows {
+        if local_today(&timezone).month() == month {
+            wanted.insert((
+                serenity::GuildId(guild.parse()?),
+                serenity::UserId(user.parse()?),
+                serenity::RoleId(role.parse()?),
+            ));
+        }
+    }
+
+    Ok(wanted)
+}
+
+async fn get_month_role_assignments(pool: &SqlitePool) -> Result<HashSet<MonthRoleAssignment>> {
+    let rows: Vec<(String, String, String)> = sqlx::query_as(include_str!(
+        "queries/get_meatball_month_role_assignments.sql"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let mut current = HashSet::new();
+    for (guild, user, role) in rows {
+        current.insert((
+            serenity::GuildId(guild.parse()?),
+            serenity::UserId(user.parse()?),
+            serenity::RoleId(role.parse()?),
+        ));
+    }
+
+    Ok(current)
+}
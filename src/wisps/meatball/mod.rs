@@ -1,2 +1,5 @@
 pub mod commands;
+pub mod dates;
 pub mod jobs;
+pub mod scheduled_events;
+pub mod settings;
@@ -0,0 +1,7 @@
+UPDATE
+    meatball_celebration
+SET
+    message_id = ?,
+    announce_pending = FALSE
+WHERE
+    guild_id = ? AND user_id = ? AND date = ? AND outcome = 'active'
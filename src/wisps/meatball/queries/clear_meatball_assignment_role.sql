@@ -0,0 +1,8 @@
+UPDATE
+    meatball_celebration
+SET
+    role_id = NULL
+WHERE
+    guild_id = ? AND
+    user_id = ? AND
+    outcome = 'active'
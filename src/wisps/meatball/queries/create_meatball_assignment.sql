@@ -1,8 +1,12 @@
 INSERT
 INTO
-    meatball_role_assignment(
+    meatball_celebration(
         guild_id,
         user_id,
-        date
+        date,
+        granted_at,
+        announce_pending,
+        role_id,
+        outcome
     )
-VALUES(?, ?, date(?))
+VALUES(?, ?, ?, ?, ?, ?, 'active')
@@ -0,0 +1,7 @@
+INSERT
+INTO meatball_month_role_assignment(
+    guild_id,
+    user_id,
+    role_id
+)
+VALUES(?, ?, ?)
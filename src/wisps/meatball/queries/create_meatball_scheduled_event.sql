@@ -0,0 +1,11 @@
+INSERT
+INTO meatball_scheduled_event(
+    guild_id,
+    user_id,
+    date,
+    event_id
+)
+VALUES(?, ?, ?, ?)
+ON CONFLICT(guild_id, user_id) DO UPDATE SET
+    date = excluded.date,
+    event_id = excluded.event_id
@@ -1,6 +1,9 @@
-DELETE
-FROM
-    meatball_role_assignment
+UPDATE
+    meatball_celebration
+SET
+    revoked_at = ?,
+    outcome = ?
 WHERE
     guild_id = ? AND
-    user_id = ?
+    user_id = ? AND
+    outcome = 'active'
@@ -0,0 +1,7 @@
+DELETE
+FROM
+    meatball_month_role_assignment
+WHERE
+    guild_id = ? AND
+    user_id = ? AND
+    role_id = ?
@@ -0,0 +1,6 @@
+DELETE
+FROM
+    meatball_scheduled_event
+WHERE
+    guild_id = ? AND
+    user_id = ?
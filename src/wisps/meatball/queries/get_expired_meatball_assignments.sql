@@ -1,5 +1,14 @@
 SELECT
-    guild_id, user_id
-FROM meatball_role_assignment
+    mc.guild_id,
+    mc.user_id,
+    mc.date,
+    mc.role_id,
+    COALESCE(gt.timezone, 'UTC')
+FROM
+    meatball_celebration as mc
+LEFT JOIN
+    guild_timezone as gt
+ON
+    mc.guild_id = gt.guild_id
 WHERE
-    date != date(?)
+    mc.outcome = 'active'
@@ -0,0 +1,6 @@
+SELECT
+    date_format
+FROM
+    guild_date_format
+WHERE
+    guild_id = ?
@@ -0,0 +1,6 @@
+SELECT
+    date_order
+FROM
+    guild_date_order
+WHERE
+    guild_id = ?
@@ -0,0 +1,6 @@
+SELECT
+    timezone
+FROM
+    guild_timezone
+WHERE
+    guild_id = ?
@@ -0,0 +1,8 @@
+SELECT
+    role_id
+FROM
+    meatball_celebration
+WHERE
+    guild_id = ? AND
+    user_id = ? AND
+    outcome = 'active'
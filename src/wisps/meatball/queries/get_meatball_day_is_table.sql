@@ -0,0 +1,9 @@
+SELECT EXISTS(
+    SELECT
+        1
+    FROM
+        sqlite_master
+    WHERE
+        type = 'table' AND
+        name = 'meatball_day'
+)
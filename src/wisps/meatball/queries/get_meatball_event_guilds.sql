@@ -0,0 +1,17 @@
+SELECT
+    g.guild_id,
+    COALESCE(gme.enabled, 0),
+    COALESCE(gt.timezone, 'UTC')
+FROM (
+    SELECT guild_id FROM guild_meatball_events WHERE enabled
+    UNION
+    SELECT guild_id FROM meatball_scheduled_event
+) as g
+LEFT JOIN
+    guild_meatball_events as gme
+ON
+    g.guild_id = gme.guild_id
+LEFT JOIN
+    guild_timezone as gt
+ON
+    g.guild_id = gt.guild_id
@@ -0,0 +1,6 @@
+SELECT
+    guild_id,
+    user_id,
+    role_id
+FROM
+    meatball_month_role_assignment
@@ -0,0 +1,7 @@
+SELECT
+    subscriber_id
+FROM
+    meatball_reminder
+WHERE
+    guild_id = ? AND
+    user_id = ?
@@ -0,0 +1,8 @@
+SELECT
+    user_id,
+    date,
+    event_id
+FROM
+    meatball_scheduled_event
+WHERE
+    guild_id = ?
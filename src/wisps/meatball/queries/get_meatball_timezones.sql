@@ -0,0 +1,11 @@
+SELECT DISTINCT
+    COALESCE(gt.timezone, 'UTC')
+FROM (
+    SELECT guild_id FROM meatball_day
+    UNION
+    SELECT guild_id FROM meatball_celebration WHERE outcome = 'active'
+) as g
+LEFT JOIN
+    guild_timezone as gt
+ON
+    g.guild_id = gt.guild_id
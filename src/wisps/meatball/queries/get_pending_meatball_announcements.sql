@@ -0,0 +1,18 @@
+SELECT
+    mc.guild_id,
+    mc.user_id,
+    mc.date,
+    COALESCE(gt.timezone, 'UTC'),
+    COALESCE(gah.hour, 0)
+FROM
+    meatball_celebration as mc
+LEFT JOIN
+    guild_timezone as gt
+ON
+    mc.guild_id = gt.guild_id
+LEFT JOIN
+    guild_announce_hour as gah
+ON
+    mc.guild_id = gah.guild_id
+WHERE
+    mc.outcome = 'active' AND mc.announce_pending
@@ -0,0 +1,23 @@
+SELECT
+    md.guild_id,
+    md.user_id,
+    md.month,
+    md.day,
+    md.announce,
+    md.role,
+    COALESCE(gt.timezone, 'UTC'),
+    (
+        SELECT MAX(mc.date)
+        FROM meatball_celebration as mc
+        WHERE mc.guild_id = md.guild_id AND mc.user_id = md.user_id
+    )
+FROM
+    meatball_day as md
+LEFT JOIN
+    guild_timezone as gt
+ON
+    md.guild_id = gt.guild_id
+WHERE
+    (md.announce OR md.role)
+    AND md.guild_id = ?
+    AND md.user_id = ?
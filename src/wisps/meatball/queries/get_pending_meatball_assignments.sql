@@ -1,14 +1,21 @@
 SELECT
     md.guild_id,
-    md.user_id
+    md.user_id,
+    md.month,
+    md.day,
+    md.announce,
+    md.role,
+    COALESCE(gt.timezone, 'UTC'),
+    (
+        SELECT MAX(mc.date)
+        FROM meatball_celebration as mc
+        WHERE mc.guild_id = md.guild_id AND mc.user_id = md.user_id
+    )
 FROM
     meatball_day as md
 LEFT JOIN
-    meatball_role_assignment as mra
+    guild_timezone as gt
 ON
-    md.guild_id = mra.guild_id
-    AND md.user_id = mra.user_id
+    md.guild_id = gt.guild_id
 WHERE
-    mra.user_id IS NULL
-    AND md.month = ?
-    AND md.day = ?
+    (md.announce OR md.role)
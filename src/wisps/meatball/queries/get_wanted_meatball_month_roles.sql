@@ -0,0 +1,19 @@
+SELECT
+    md.guild_id,
+    md.user_id,
+    md.month,
+    mmr.role_id,
+    COALESCE(gt.timezone, 'UTC')
+FROM
+    meatball_day as md
+JOIN
+    meatball_month_role as mmr
+ON
+    md.guild_id = mmr.guild_id
+    AND md.month = mmr.month
+LEFT JOIN
+    guild_timezone as gt
+ON
+    md.guild_id = gt.guild_id
+WHERE
+    md.role
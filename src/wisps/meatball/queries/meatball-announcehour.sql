@@ -0,0 +1,8 @@
+INSERT
+INTO guild_announce_hour(
+    guild_id,
+    hour
+)
+VALUES(?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    hour = excluded.hour
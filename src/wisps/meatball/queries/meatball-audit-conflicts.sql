@@ -0,0 +1,17 @@
+SELECT
+    c.user_id,
+    c.month,
+    c.day,
+    p.month,
+    p.day
+FROM
+    meatball_profile_conflict as c
+JOIN
+    meatball_profile as p
+ON
+    c.user_id = p.user_id
+WHERE
+    c.guild_id = ?
+ORDER BY
+    c.user_id
+LIMIT 10
@@ -0,0 +1,8 @@
+SELECT
+    user_id,
+    date
+FROM
+    meatball_celebration
+WHERE
+    guild_id = ? AND
+    date >= ?
@@ -0,0 +1,9 @@
+SELECT
+    user_id,
+    month,
+    day
+FROM
+    meatball_day
+WHERE
+    guild_id = ? AND
+    (announce OR role)
@@ -0,0 +1,15 @@
+SELECT
+    user_id,
+    date,
+    granted_at,
+    revoked_at,
+    message_id,
+    outcome
+FROM
+    meatball_celebration
+WHERE
+    guild_id = ?
+ORDER BY
+    date DESC,
+    granted_at DESC
+LIMIT 10
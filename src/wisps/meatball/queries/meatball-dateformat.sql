@@ -0,0 +1,8 @@
+INSERT
+INTO guild_date_format(
+    guild_id,
+    date_format
+)
+VALUES(?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    date_format = excluded.date_format
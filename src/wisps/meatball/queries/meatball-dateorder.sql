@@ -0,0 +1,8 @@
+INSERT
+INTO guild_date_order(
+    guild_id,
+    date_order
+)
+VALUES(?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    date_order = excluded.date_order
@@ -0,0 +1,8 @@
+INSERT
+INTO guild_meatball_events(
+    guild_id,
+    enabled
+)
+VALUES(?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    enabled = excluded.enabled
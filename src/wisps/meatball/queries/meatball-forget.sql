@@ -1,5 +1,5 @@
 DELETE
-FROM meatball_day
+FROM meatball_membership
 WHERE
     guild_id = ? AND
     user_id = ?
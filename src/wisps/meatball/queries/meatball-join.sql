@@ -0,0 +1,5 @@
+INSERT INTO meatball_membership (
+       guild_id,
+       user_id
+) VALUES (?, ?)
+ON CONFLICT(guild_id, user_id) DO NOTHING
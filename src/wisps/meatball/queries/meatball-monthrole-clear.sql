@@ -0,0 +1,5 @@
+DELETE
+FROM meatball_month_role
+WHERE
+    guild_id = ? AND
+    month = ?
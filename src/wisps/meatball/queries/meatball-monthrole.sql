@@ -0,0 +1,9 @@
+INSERT
+INTO meatball_month_role(
+    guild_id,
+    month,
+    role_id
+)
+VALUES(?, ?, ?)
+ON CONFLICT(guild_id, month) DO UPDATE SET
+    role_id = excluded.role_id
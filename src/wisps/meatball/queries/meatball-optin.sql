@@ -0,0 +1,9 @@
+INSERT INTO meatball_membership (
+       guild_id,
+       user_id,
+       announce,
+       role
+) VALUES (?, ?, ?, ?)
+ON CONFLICT(guild_id, user_id) DO UPDATE SET
+   announce = excluded.announce,
+   role = excluded.role
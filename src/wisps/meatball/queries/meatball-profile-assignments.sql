@@ -0,0 +1,7 @@
+SELECT
+    guild_id
+FROM
+    meatball_celebration
+WHERE
+    user_id = ? AND
+    outcome = 'active'
@@ -0,0 +1,4 @@
+DELETE
+FROM meatball_profile
+WHERE
+    user_id = ?
@@ -0,0 +1,8 @@
+SELECT
+    guild_id,
+    announce,
+    role
+FROM
+    meatball_membership
+WHERE
+    user_id = ?
@@ -0,0 +1,7 @@
+SELECT
+    month,
+    day
+FROM
+    meatball_profile
+WHERE
+    user_id = ?
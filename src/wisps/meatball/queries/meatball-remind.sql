@@ -0,0 +1,8 @@
+INSERT
+INTO meatball_reminder(
+    guild_id,
+    user_id,
+    subscriber_id
+)
+VALUES(?, ?, ?)
+ON CONFLICT(guild_id, user_id, subscriber_id) DO NOTHING
@@ -1,9 +1,8 @@
-INSERT INTO meatball_day (
-       guild_id,
+INSERT INTO meatball_profile (
        user_id,
        month,
        day
-) VALUES (?, ?, ?, ?)
-ON CONFLICT(guild_id, user_id) DO UPDATE SET
+) VALUES (?, ?, ?)
+ON CONFLICT(user_id) DO UPDATE SET
    month = excluded.month,
    day = excluded.day
@@ -0,0 +1,7 @@
+SELECT
+    COUNT(*)
+FROM
+    meatball_celebration
+WHERE
+    guild_id = ? AND
+    outcome = 'completed'
@@ -0,0 +1,11 @@
+SELECT
+    month,
+    day,
+    COUNT(*)
+FROM
+    meatball_day
+WHERE
+    guild_id = ?
+GROUP BY
+    month,
+    day
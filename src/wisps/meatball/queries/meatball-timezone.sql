@@ -0,0 +1,8 @@
+INSERT
+INTO guild_timezone(
+    guild_id,
+    timezone
+)
+VALUES(?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    timezone = excluded.timezone
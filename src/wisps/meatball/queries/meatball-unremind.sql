@@ -0,0 +1,5 @@
+DELETE FROM meatball_reminder
+WHERE
+    guild_id = ? AND
+    user_id = ? AND
+    subscriber_id = ?
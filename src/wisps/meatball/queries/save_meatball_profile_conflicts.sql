@@ -0,0 +1,25 @@
+-- must match the day migration 0006 keeps for each member: the one they added
+-- most recently.
+CREATE TABLE IF NOT EXISTS meatball_profile_conflict (
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    month INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+INSERT OR IGNORE INTO meatball_profile_conflict (guild_id, user_id, month, day)
+SELECT d.guild_id, d.user_id, d.month, d.day
+FROM
+    meatball_day as d
+JOIN
+    meatball_day as kept
+ON
+    kept.rowid = (
+        SELECT MAX(rowid)
+        FROM meatball_day
+        WHERE user_id = d.user_id
+    )
+WHERE
+    d.month != kept.month OR
+    d.day != kept.day;
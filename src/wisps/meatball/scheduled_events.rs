@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use color_eyre::{
+    eyre::{eyre, ErrReport},
+    Result,
+};
+use poise::serenity_prelude as serenity;
+use tracing::{error, info, warn};
+
+use super::{
+    dates,
+    jobs::{is_unknown_member, parse_timezone},
+};
+use crate::bot::JobContext;
+
+/// How far ahead to create events for.
+const EVENT_WINDOW_DAYS: i64 = 30;
+
+/// How long to trust that a member is still in a guild before asking discord
+/// again.
+const MEMBER_RECHECK_HOURS: i64 = 24;
+
+/// When each member was last seen in each guild.
+pub type MemberSightings = HashMap<(serenity::GuildId, serenity::UserId), DateTime<Utc>>;
+
+/// Keeps each opted-in guild's scheduled events in line with the upcoming
+/// meatball days, and cleans up after guilds that have opted out.
+pub async fn sync_scheduled_events(ctx: &JobContext) -> Result<()> {
+    let guilds: Vec<(String, bool, String)> =
+        sqlx::query_as(include_str!("queries/get_meatball_event_guilds.sql"))
+            .fetch_all(&ctx.db)
+            .await?;
+
+    for (guild, enabled, timezone) in guilds {
+        let guild = serenity::GuildId(guild.parse()?);
+        if let Err(e) = sync_guild(guild, enabled, parse_timezone(&timezone), ctx).await {
+            error!("Failed to sync meatball events for guild {guild}: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn sync_guild(
+    guild: serenity::GuildId,
+    enabled: bool,
+    timezone: Tz,
+    ctx: &JobContext,
+) -> Result<()> {
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+
+    let mut wanted = if enabled {
+        get_upcoming_days(guild, today, ctx).await?
+    } else {
+        HashMap::new()
+    };
+
+    let existing: Vec<(String, NaiveDate, String)> =
+        sqlx::query_as(include_str!("queries/get_meatball_scheduled_events.sql"))
+            .bind(guild.to_string())
+            .fetch_all(&ctx.db)
+            .await?;
+
+    for (user, date, event) in existing {
+        let user = serenity::UserId(user.parse()?);
+        if let Err(e) = sync_event(guild, user, date, &event, today, &mut wanted, ctx).await {
+            error!("Failed to sync meatball day event for user '{user}' in guild {guild}: {e}");
+        }
+    }
+
+    for (user, date) in wanted {
+        if let Err(e) = create_event(guild, user, date, timezone, ctx).await {
+            error!("Failed to create meatball day event for user '{user}' in guild {guild}: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Keeps, moves or removes an existing event. Anything left in `wanted`
+/// afterwards still needs creating.
+async fn sync_event(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    date: NaiveDate,
+    event: &str,
+    today: NaiveDate,
+    wanted: &mut HashMap<serenity::UserId, NaiveDate>,
+    ctx: &JobContext,
+) -> Result<()> {
+    if date == today {
+        // leave today's event running, discord ends it on its own.
+        wanted.remove(&user);
+        return Ok(());
+    }
+
+    if date < today {
+        return drop_event(guild, user, ctx).await;
+    }
+
+    let event = serenity::ScheduledEventId(event.parse()?);
+    if wanted.get(&user) != Some(&date) {
+        return delete_event(guild, user, event, ctx).await;
+    }
+
+    wanted.remove(&user);
+    if !is_member(guild, user, ctx).await? {
+        info!("User '{user}' has left guild '{guild}', removing their event");
+        delete_event(guild, user, event, ctx).await?;
+    }
+
+    Ok(())
+}
+
+/// Whether the member is still in the guild. Syncing runs often, so members
+/// in the cache or seen in the last day are taken as still being there rather
+/// than asking discord about every event's member every time.
+async fn is_member(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<bool> {
+    if ctx.ctx.cache.member(guild, user).is_some() {
+        return Ok(true);
+    }
+
+    let recently_seen = ctx
+        .member_sightings
+        .lock()
+        .await
+        .get(&(guild, user))
+        .is_some_and(|seen| Utc::now() - *seen < Duration::hours(MEMBER_RECHECK_HOURS));
+    if recently_seen {
+        return Ok(true);
+    }
+
+    Ok(find_member(guild, user, ctx).await?.is_some())
+}
+
+/// Looks up a member of the guild, or `None` if they've left.
+async fn find_member(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<Option<serenity::Member>> {
+    let member = match guild.member(&ctx.ctx, user).await {
+        Ok(member) => Some(member),
+        Err(e) => {
+            let e = ErrReport::from(e);
+            if !is_unknown_member(&e) {
+                return Err(e);
+            }
+            None
+        }
+    };
+
+    if member.is_some() {
+        ctx.member_sightings
+            .lock()
+            .await
+            .insert((guild, user), Utc::now());
+    } else {
+        ctx.member_sightings.lock().await.remove(&(guild, user));
+    }
+
+    Ok(member)
+}
+
+async fn get_upcoming_days(
+    guild: serenity::GuildId,
+    today: NaiveDate,
+    ctx: &JobContext,
+) -> Result<HashMap<serenity::UserId, NaiveDate>> {
+    let rows: Vec<(String, u32, u32)> =
+        sqlx::query_as(include_str!("queries/get_guild_meatball_days.sql"))
+            .bind(guild.to_string())
+            .fetch_all(&ctx.db)
+            .await?;
+
+    let window_end = today + Duration::days(EVENT_WINDOW_DAYS);
+
+    let mut upcoming = HashMap::new();
+    for (user, month, day) in rows {
+        if let Some(date) = dates::next_occurrence(month, day, today) {
+            if date <= window_end {
+                upcoming.insert(serenity::UserId(user.parse()?), date);
+            }
+        }
+    }
+
+    Ok(upcoming)
+}
+
+async fn create_event(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    date: NaiveDate,
+    timezone: Tz,
+    ctx: &JobContext,
+) -> Result<()> {
+    let Some(member) = find_member(guild, user, ctx).await? else {
+        info!("User '{user}' has left guild '{guild}', not creating their event");
+        return Ok(());
+    };
+    let name = member.display_name();
+
+    let start = date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| timezone.from_local_datetime(&midnight).earliest())
+        .ok_or(eyre!("{date} has no midnight in {timezone}"))?
+        .with_timezone(&Utc);
+    let end = start + Duration::days(1);
+
+    info!("Creating meatball day event for member '{name}' of guild '{guild}' on {date}");
+    let event = guild
+        .create_scheduled_event(&ctx.ctx.http, |event| {
+            event
+                .name(format!("{name}'s meatball day"))
+                .description(format!("Wish {name} a happy meatball day! 🎉"))
+                .kind(serenity::ScheduledEventType::External)
+                .location("Everywhere 🍝")
+                .start_time(start)
+                .end_time(end)
+        })
+        .await?;
+
+    sqlx::query(include_str!("queries/create_meatball_scheduled_event.sql"))
+        .bind(guild.to_string())
+        .bind(user.to_string())
+        .bind(date)
+        .bind(event.id.to_string())
+        .execute(&ctx.db)
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_event(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    event: serenity::ScheduledEventId,
+    ctx: &JobContext,
+) -> Result<()> {
+    info!("Deleting meatball day event '{event}' from guild '{guild}'");
+    if let Err(e) = guild.delete_scheduled_event(&ctx.ctx.http, event).await {
+        // it may well have been deleted by hand already.
+        warn!("Failed to delete meatball day event '{event}': {e}");
+    }
+
+    drop_event(guild, user, ctx).await
+}
+
+async fn drop_event(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    ctx: &JobContext,
+) -> Result<()> {
+    sqlx::query(include_str!("queries/drop_meatball_scheduled_event.sql"))
+        .bind(guild.to_string())
+        .bind(user.to_string())
+        .execute(&ctx.db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use chrono_tz::Tz;
+use color_eyre::Result;
+use poise::serenity_prelude as serenity;
+use sqlx::SqlitePool;
+
+use super::{
+    dates::{DateFormat, DateOrder},
+    jobs::parse_timezone,
+};
+
+pub async fn get_date_order(guild: serenity::GuildId, pool: &SqlitePool) -> Result<DateOrder> {
+    let row: Option<(String,)> = sqlx::query_as(include_str!("queries/get_guild_date_order.sql"))
+        .bind(guild.to_string())
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row
+        .and_then(|(order,)| DateOrder::from_setting(&order))
+        .unwrap_or_default())
+}
+
+pub async fn get_date_format(guild: serenity::GuildId, pool: &SqlitePool) -> Result<DateFormat> {
+    let row: Option<(String,)> = sqlx::query_as(include_str!("queries/get_guild_date_format.sql"))
+        .bind(guild.to_string())
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row
+        .and_then(|(format,)| DateFormat::from_setting(&format))
+        .unwrap_or_default())
+}
+
+pub async fn get_timezone(guild: serenity::GuildId, pool: &SqlitePool) -> Result<Tz> {
+    let row: Option<(String,)> = sqlx::query_as(include_str!("queries/get_guild_timezone.sql"))
+        .bind(guild.to_string())
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map_or(Tz::UTC, |(timezone,)| parse_timezone(&timezone)))
+}
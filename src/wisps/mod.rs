@@ -1,6 +1,14 @@
+pub mod events;
 pub mod meatball;
 pub mod wordle;
 
 pub fn commands() -> Vec<poise::Command<crate::Bot, color_eyre::eyre::ErrReport>> {
-    vec![meatball::commands::meatball()]
+    vec![
+        meatball::commands::meatball(),
+        meatball::commands::lookup_user(),
+        meatball::commands::remind_me(),
+        events::commands::event(),
+        events::commands::countdown(),
+        wordle::commands::wordle(),
+    ]
 }
@@ -0,0 +1,698 @@
+use std::cmp::Ordering;
+
+use chrono::{Duration, Utc};
+use color_eyre::{eyre::eyre, Result};
+use itertools::Itertools;
+use poise::serenity_prelude as serenity;
+
+use super::{results, rules, streaks};
+use crate::bot::CommandContext;
+
+/// How many members to show on each page of a leaderboard.
+const LEADERBOARD_PAGE_SIZE: usize = 10;
+
+/// How many custom rules to show on each page of the list, keeping pages with
+/// long patterns under the message limit.
+const RULES_PAGE_SIZE: usize = 5;
+
+/// How many of the latest puzzles to show the trend for.
+const TREND_PUZZLES: usize = 30;
+
+/// How wide the longest bar in a guess distribution is.
+const DISTRIBUTION_WIDTH: usize = 20;
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "leaderboard",
+        "streaks",
+        "stats",
+        "summary",
+        "games",
+        "rule",
+        "reload"
+    )
+)]
+#[allow(clippy::unused_async)]
+pub async fn wordle(_ctx: CommandContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// How far back to look for results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum Period {
+    #[name = "Last 7 days"]
+    Week,
+    #[name = "Last 30 days"]
+    Month,
+    #[name = "All time"]
+    All,
+}
+
+impl Period {
+    fn cutoff(self) -> Option<chrono::DateTime<Utc>> {
+        match self {
+            Self::Week => Some(Utc::now() - Duration::days(7)),
+            Self::Month => Some(Utc::now() - Duration::days(30)),
+            Self::All => None,
+        }
+    }
+
+    const fn describe(self) -> &'static str {
+        match self {
+            Self::Week => "last 7 days",
+            Self::Month => "last 30 days",
+            Self::All => "all time",
+        }
+    }
+}
+
+/// User, games played, games solved and average score.
+type LeaderboardRow = (String, u32, u32, Option<f64>);
+
+/// Show who's doing best at a puzzle game in this server.
+#[poise::command(slash_command)]
+pub async fn leaderboard(
+    ctx: CommandContext<'_>,
+    #[description = "The game to rank"]
+    #[autocomplete = "autocomplete_game"]
+    game: String,
+    #[description = "How far back to look"] period: Option<Period>,
+    #[description = "How many games someone needs to have played to be ranked"]
+    #[min = 1]
+    min_games: Option<u32>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let period = period.unwrap_or(Period::Week);
+
+    if !results::game_ids().contains(&game) {
+        ctx.say("I don't know that game... :thinking:").await?;
+        return Ok(());
+    }
+
+    let rows: Vec<LeaderboardRow> = sqlx::query_as(include_str!("queries/wordle-leaderboard.sql"))
+        .bind(guild.id.to_string())
+        .bind(&game)
+        .bind(period.cutoff())
+        .bind(min_games.unwrap_or(3))
+        .fetch_all(&ctx.data().db)
+        .await?;
+
+    if rows.is_empty() {
+        ctx.say(format!("Nobody has played enough {game} to be ranked yet!"))
+            .await?;
+        return Ok(());
+    }
+
+    let higher_is_better = results::higher_is_better(&game);
+    let lines = rows
+        .into_iter()
+        .sorted_by(|a, b| compare_rows(a, b, higher_is_better))
+        .enumerate()
+        .map(|(rank, (user, played, solved, average))| {
+            let user = user.parse().map_or_else(
+                |_| user.clone(),
+                |user| serenity::Mention::from(serenity::UserId(user)).to_string(),
+            );
+            let average = average.map_or_else(|| "-".to_owned(), |average| format!("{average:.2}"));
+            format!(
+                "{}. {user}: average {average}, solved {solved}/{played} ({:.0}%)",
+                rank + 1,
+                solve_rate(solved, played) * 100.0
+            )
+        })
+        .collect_vec();
+
+    let pages = lines
+        .chunks(LEADERBOARD_PAGE_SIZE)
+        .map(|chunk| {
+            format!(
+                "**{game}** leaderboard ({})\n{}",
+                period.describe(),
+                chunk.join("\n")
+            )
+        })
+        .collect_vec();
+    let pages = pages.iter().map(String::as_str).collect_vec();
+
+    poise::builtins::paginate(ctx, &pages).await?;
+
+    Ok(())
+}
+
+/// Show someone's current and best puzzle streaks in this server.
+#[poise::command(slash_command)]
+pub async fn streaks(
+    ctx: CommandContext<'_>,
+    #[description = "Whose streaks to show, defaults to you"] user: Option<serenity::User>,
+    #[description = "Only show streaks for this game"]
+    #[autocomplete = "autocomplete_game"]
+    game: Option<String>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let user = user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let streaks = streaks::get_streaks(guild.id, user.id, game.as_deref(), &ctx.data().db).await?;
+
+    if streaks.is_empty() {
+        ctx.say(format!(
+            "{} hasn't posted any puzzles yet!",
+            serenity::Mention::from(user.id)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let lines = streaks
+        .into_iter()
+        .sorted_by_key(|(_, streak)| std::cmp::Reverse(streak.current))
+        .map(|(game, streak)| {
+            let milestone = streaks::MILESTONES
+                .iter()
+                .rev()
+                .find(|(length, _)| streak.current >= *length)
+                .map_or_else(String::new, |(_, emoji)| format!(" {emoji}"));
+            format!(
+                "**{game}**: {} in a row{milestone} (best {})",
+                streak.current, streak.best
+            )
+        })
+        .join("\n");
+
+    ctx.say(format!(
+        "{}'s puzzle streaks:\n{lines}",
+        serenity::Mention::from(user.id)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Score, max score and whether it was failed.
+type StatsRow = (Option<u32>, Option<u32>, bool);
+
+/// Show someone's results for a puzzle game in this server.
+#[poise::command(slash_command)]
+pub async fn stats(
+    ctx: CommandContext<'_>,
+    #[description = "Whose stats to show, defaults to you"] user: Option<serenity::User>,
+    #[description = "The game to show, defaults to wordle"]
+    #[autocomplete = "autocomplete_game"]
+    game: Option<String>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+    let user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let game = game.unwrap_or_else(|| "wordle".to_owned());
+
+    let rows: Vec<StatsRow> = sqlx::query_as(include_str!("queries/wordle-stats.sql"))
+        .bind(guild.id.to_string())
+        .bind(user.id.to_string())
+        .bind(&game)
+        .fetch_all(&ctx.data().db)
+        .await?;
+
+    if rows.is_empty() {
+        ctx.say(format!(
+            "{} hasn't posted any {game} results yet!",
+            serenity::Mention::from(user.id)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let higher_is_better = results::higher_is_better(&game);
+    let played = rows.len();
+    let solved = rows.iter().filter(|(_, _, failed)| !failed).count();
+    let scores = solved_scores(&rows);
+
+    let mut lines = vec![format!(
+        "{}'s **{game}** stats",
+        serenity::Mention::from(user.id)
+    )];
+    lines.push(format!(
+        "Played {played}, solved {solved} ({:.0}%)",
+        ratio(solved, played) * 100.0
+    ));
+
+    if let Some(average) = average(&scores) {
+        let (best, worst) = if higher_is_better {
+            (scores.iter().max(), scores.iter().min())
+        } else {
+            (scores.iter().min(), scores.iter().max())
+        };
+        if let (Some(best), Some(worst)) = (best, worst) {
+            lines.push(format!(
+                "Average score {average:.2}, best {best}, worst {worst}"
+            ));
+        }
+    }
+
+    let (best_run, worst_run) = runs(&rows);
+    lines.push(format!(
+        "Longest run of solves {best_run}, longest run of fails {worst_run}"
+    ));
+
+    lines.push(format!(
+        "```\n{}\n```",
+        distribution(&rows, higher_is_better)
+    ));
+
+    let recent = &rows[rows.len().saturating_sub(TREND_PUZZLES)..];
+    lines.push(format!(
+        "Last {} puzzles: `{}`",
+        recent.len(),
+        sparkline(recent, higher_is_better)
+    ));
+    let recent_scores = solved_scores(recent);
+    if let (Some(recent), Some(overall)) = (average(&recent_scores), average(&scores)) {
+        let improving = if higher_is_better {
+            recent > overall
+        } else {
+            recent < overall
+        };
+        lines.push(format!(
+            "Recent average {recent:.2} against {overall:.2} overall{}",
+            if improving {
+                " :chart_with_upwards_trend:"
+            } else {
+                ""
+            }
+        ));
+    }
+
+    ctx.say(lines.join("\n")).await?;
+
+    Ok(())
+}
+
+/// Post a summary of yesterday's puzzles every day, or stop posting it.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn summary(
+    ctx: CommandContext<'_>,
+    #[description = "The channel to post in, leave empty to stop posting"] channel: Option<
+        serenity::GuildChannel,
+    >,
+    #[description = "The hour to post at in the server's timezone, from 0 to 23"]
+    #[min = 0]
+    #[max = 23]
+    hour: Option<u8>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let Some(channel) = channel else {
+        sqlx::query(include_str!("queries/wordle-summary-clear.sql"))
+            .bind(guild.id.to_string())
+            .execute(&ctx.data().db)
+            .await?;
+        ctx.say("I will stop posting puzzle summaries.").await?;
+        return Ok(());
+    };
+
+    if channel.kind != serenity::ChannelType::Text {
+        ctx.say("I can only post in normal text channels.").await?;
+        return Ok(());
+    }
+
+    let hour = hour.unwrap_or(9);
+    sqlx::query(include_str!("queries/wordle-summary.sql"))
+        .bind(guild.id.to_string())
+        .bind(channel.id.to_string())
+        .bind(hour)
+        .execute(&ctx.data().db)
+        .await?;
+
+    ctx.say(format!(
+        "I will post a summary of yesterday's puzzles in {} at {hour:02}:00 every day. :newspaper:",
+        serenity::Mention::from(channel.id)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Turn recognising and reacting to a puzzle game on or off, or see which are
+/// on.
+#[poise::command(slash_command, required_permissions = "ADMINISTRATOR")]
+pub async fn games(
+    ctx: CommandContext<'_>,
+    #[description = "The game to turn on or off"]
+    #[autocomplete = "autocomplete_game"]
+    game: Option<String>,
+    #[description = "Whether to recognise and react to the game"] enabled: Option<bool>,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let (Some(game), Some(enabled)) = (game, enabled) else {
+        let settings = ctx
+            .data()
+            .puzzle_settings
+            .get(guild.id, &ctx.data().db)
+            .await?;
+        let lines = results::game_ids()
+            .into_iter()
+            .map(|game| {
+                let status = if settings.is_enabled(&game) {
+                    ":white_check_mark:"
+                } else {
+                    ":no_entry_sign:"
+                };
+                format!("{status} {game}")
+            })
+            .join("\n");
+        ctx.say(lines).await?;
+        return Ok(());
+    };
+
+    if !results::game_ids().contains(&game) {
+        ctx.say("I don't know that game... :thinking:").await?;
+        return Ok(());
+    }
+
+    sqlx::query(include_str!("queries/wordle-games.sql"))
+        .bind(guild.id.to_string())
+        .bind(&game)
+        .bind(enabled)
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().puzzle_settings.invalidate(guild.id).await;
+
+    if enabled {
+        ctx.say(format!("I will react to {game} posts again."))
+            .await?;
+    } else {
+        ctx.say(format!("I will ignore {game} posts from now on."))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("rule_add", "rule_list", "rule_remove"),
+    required_permissions = "ADMINISTRATOR"
+)]
+#[allow(clippy::unused_async)]
+pub async fn rule(_ctx: CommandContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// React with an emoji to any message matching a pattern.
+#[poise::command(slash_command, rename = "add", required_permissions = "ADMINISTRATOR")]
+pub async fn rule_add(
+    ctx: CommandContext<'_>,
+    #[description = "A regular expression to look for, ignoring case"] pattern: String,
+    #[description = "The emoji to react with, can be one of this server's"] emoji: String,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let settings = ctx
+        .data()
+        .puzzle_settings
+        .get(guild.id, &ctx.data().db)
+        .await?;
+    if settings.rules.len() >= rules::MAX_RULES_PER_GUILD {
+        ctx.say(format!(
+            "This server already has {} rules, remove one first.",
+            rules::MAX_RULES_PER_GUILD
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if let Err(e) = rules::compile_pattern(&pattern) {
+        ctx.say(format!("I can't use that pattern: {e}")).await?;
+        return Ok(());
+    }
+
+    let reaction = match rules::parse_emoji(&emoji) {
+        Ok(reaction) => reaction,
+        Err(e) => {
+            ctx.say(format!("I can't use that emoji: {e}")).await?;
+            return Ok(());
+        }
+    };
+
+    // discord only lets us react with custom emoji we can see.
+    if let serenity::ReactionType::Custom { id, .. } = reaction {
+        if !guild.emojis.contains_key(&id) {
+            ctx.say("I can only react with this server's own emoji.")
+                .await?;
+            return Ok(());
+        }
+    }
+
+    sqlx::query(include_str!("queries/wordle-rule-add.sql"))
+        .bind(guild.id.to_string())
+        .bind(&pattern)
+        .bind(emoji.trim())
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().puzzle_settings.invalidate(guild.id).await;
+
+    ctx.say(format!(
+        "I will react with {reaction} to messages matching {}.",
+        inline_code(&pattern)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Show this server's custom reaction rules.
+#[poise::command(slash_command, rename = "list", required_permissions = "ADMINISTRATOR")]
+pub async fn rule_list(ctx: CommandContext<'_>) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let settings = ctx
+        .data()
+        .puzzle_settings
+        .get(guild.id, &ctx.data().db)
+        .await?;
+
+    if settings.rules.is_empty() {
+        ctx.say("This server has no custom rules.").await?;
+        return Ok(());
+    }
+
+    let pages = settings
+        .rules
+        .chunks(RULES_PAGE_SIZE)
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|rule| {
+                    format!(
+                        "{}: {} → {}",
+                        rule.id,
+                        inline_code(rule.pattern.as_str()),
+                        rule.reaction
+                    )
+                })
+                .join("\n")
+        })
+        .collect_vec();
+    let pages = pages.iter().map(String::as_str).collect_vec();
+
+    poise::builtins::paginate(ctx, &pages).await?;
+
+    Ok(())
+}
+
+/// Formats text as inline code even if it has backticks of its own. Those are
+/// split up with zero width spaces so they can't close the span early.
+fn inline_code(text: &str) -> String {
+    format!("`` {} ``", text.replace('`', "`\u{200b}"))
+}
+
+/// Stop using a custom reaction rule.
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn rule_remove(
+    ctx: CommandContext<'_>,
+    #[description = "The rule's number, as shown by /wordle rule list"] id: i64,
+) -> Result<()> {
+    let guild = ctx.guild().ok_or(eyre!("Command run without guild"))?;
+
+    let result = sqlx::query(include_str!("queries/wordle-rule-remove.sql"))
+        .bind(guild.id.to_string())
+        .bind(id)
+        .execute(&ctx.data().db)
+        .await?;
+    ctx.data().puzzle_settings.invalidate(guild.id).await;
+
+    if result.rows_affected() == 0 {
+        ctx.say(format!("This server has no rule {id}.")).await?;
+    } else {
+        ctx.say(format!("I have removed rule {id}.")).await?;
+    }
+
+    Ok(())
+}
+
+/// Load the puzzle games file again.
+#[poise::command(slash_command, owners_only, ephemeral)]
+pub async fn reload(ctx: CommandContext<'_>) -> Result<()> {
+    match results::reload() {
+        Ok(count) => {
+            ctx.say(format!("I have loaded {count} puzzle games."))
+                .await?;
+        }
+        Err(e) => {
+            ctx.say(format!(
+                "I couldn't load the puzzle games, so I'm keeping the old ones: {e}"
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// The scores of solved puzzles. Fails can carry a score too, but like the
+/// distribution, the numbers only describe solves.
+fn solved_scores(rows: &[StatsRow]) -> Vec<u32> {
+    rows.iter()
+        .filter(|(_, _, failed)| !failed)
+        .filter_map(|(score, _, _)| *score)
+        .collect()
+}
+
+fn average(scores: &[u32]) -> Option<f64> {
+    if scores.is_empty() {
+        return None;
+    }
+    let total: u32 = scores.iter().sum();
+    Some(ratio(total as usize, scores.len()))
+}
+
+/// The longest runs of solves and of fails, in puzzle order.
+fn runs(rows: &[StatsRow]) -> (usize, usize) {
+    rows.iter()
+        .group_by(|(_, _, failed)| *failed)
+        .into_iter()
+        .fold((0, 0), |(solves, fails), (failed, run)| {
+            let length = run.count();
+            if failed {
+                (solves, fails.max(length))
+            } else {
+                (solves.max(length), fails)
+            }
+        })
+}
+
+/// A bar chart of how many times each score came up, with fails at the
+/// bottom.
+fn distribution(rows: &[StatsRow], higher_is_better: bool) -> String {
+    let counts = rows
+        .iter()
+        .map(|(score, _, failed)| if *failed { None } else { *score })
+        .counts();
+
+    let max_score = rows.iter().filter_map(|(_, max, _)| *max).max();
+    // points, like waffle's stars, can be zero. guesses can't.
+    let min_score = u32::from(!higher_is_better && !counts.contains_key(&Some(0)));
+    let mut labels = match max_score {
+        // show every score for games with a handful of guesses, like wordle.
+        Some(max) if max <= 10 => (min_score..=max).map(Some).collect_vec(),
+        _ => counts.keys().copied().flatten().sorted().collect_vec(),
+    };
+    if rows.iter().any(|(_, _, failed)| *failed) {
+        labels.push(None);
+    }
+
+    let most = counts.values().copied().max().unwrap_or(1);
+    let width = labels
+        .iter()
+        .map(|label| label.map_or(1, |score| score.to_string().len()))
+        .max()
+        .unwrap_or(1);
+
+    labels
+        .into_iter()
+        .map(|label| {
+            let count = counts.get(&label).copied().unwrap_or(0);
+            let bar = "█".repeat(bar_length(count, most));
+            let label = label.map_or_else(|| "X".to_owned(), |score| score.to_string());
+            format!("{label:>width$} | {bar} {count}")
+        })
+        .join("\n")
+}
+
+fn bar_length(count: usize, most: usize) -> usize {
+    (count * DISTRIBUTION_WIDTH).div_ceil(most.max(1))
+}
+
+/// One block per puzzle, taller for better scores, with a cross for fails.
+fn sparkline(rows: &[StatsRow], higher_is_better: bool) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let scores = rows.iter().filter_map(|(score, _, _)| *score);
+    let (Some(lowest), Some(highest)) = (scores.clone().min(), scores.max()) else {
+        return rows.iter().map(|_| '×').collect();
+    };
+
+    rows.iter()
+        .map(|(score, _, failed)| match score {
+            Some(score) if !failed => {
+                let range = (highest - lowest).max(1);
+                let mut height = (score - lowest) * 7 / range;
+                if !higher_is_better {
+                    height = 7 - height;
+                }
+                BLOCKS[height as usize]
+            }
+            _ => '×',
+        })
+        .collect()
+}
+
+#[allow(clippy::unused_async)]
+async fn autocomplete_game(
+    _ctx: CommandContext<'_>,
+    partial: &str,
+) -> impl Iterator<Item = String> {
+    let partial = partial.to_lowercase();
+    results::game_ids()
+        .into_iter()
+        .filter(move |game| game.starts_with(&partial))
+}
+
+fn solve_rate(solved: u32, played: u32) -> f64 {
+    if played == 0 {
+        0.0
+    } else {
+        f64::from(solved) / f64::from(played)
+    }
+}
+
+/// Ranks by solve rate, then average score, then games played.
+fn compare_rows(a: &LeaderboardRow, b: &LeaderboardRow, higher_is_better: bool) -> Ordering {
+    let (_, a_played, a_solved, a_average) = a;
+    let (_, b_played, b_solved, b_average) = b;
+
+    let by_average = match (a_average, b_average) {
+        (Some(a), Some(b)) if higher_is_better => b.total_cmp(a),
+        (Some(a), Some(b)) => a.total_cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+
+    solve_rate(*b_solved, *b_played)
+        .total_cmp(&solve_rate(*a_solved, *a_played))
+        .then(by_average)
+        .then(b_played.cmp(a_played))
+}
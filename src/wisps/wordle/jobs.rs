@@ -0,0 +1,195 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use color_eyre::{
+    eyre::{eyre, ErrReport},
+    Result,
+};
+use itertools::Itertools;
+use poise::serenity_prelude as serenity;
+use tracing::{error, info};
+
+use super::results;
+use crate::{bot::JobContext, wisps::meatball::jobs::parse_timezone};
+
+/// How many members to name in each of a game's lists, keeping its line short.
+const SUMMARY_MENTIONS: usize = 30;
+
+/// The most a discord message can hold.
+const MESSAGE_LIMIT: usize = 2000;
+
+/// Guild, channel, hour to post at, the last day posted and the timezone.
+type SummaryRow = (String, String, u32, Option<NaiveDate>, String);
+
+/// Game, user, score and whether it was failed.
+type ResultRow = (String, String, Option<u32>, bool);
+
+/// Posts a summary of yesterday's puzzle results in each guild that wants one,
+/// once the guild's chosen hour has come around.
+pub async fn post_summaries(ctx: JobContext) -> Result<()> {
+    let rows: Vec<SummaryRow> = sqlx::query_as(include_str!("queries/get_puzzle_summaries.sql"))
+        .fetch_all(&ctx.db)
+        .await?;
+
+    let now = Utc::now();
+    for (guild, channel, hour, last_posted, timezone) in rows {
+        let timezone = parse_timezone(&timezone);
+        let local = now.with_timezone(&timezone);
+        let today = local.date_naive();
+
+        if local.hour() < hour || last_posted == Some(today) {
+            continue;
+        }
+
+        if let Err(e) = post_summary(&guild, &channel, today, timezone, &ctx).await {
+            error!("Failed to post puzzle summary for guild {guild}: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn post_summary(
+    guild: &str,
+    channel: &str,
+    today: NaiveDate,
+    timezone: Tz,
+    ctx: &JobContext,
+) -> Result<()> {
+    let yesterday = today
+        .pred_opt()
+        .ok_or_else(|| eyre!("no day before {today}"))?;
+
+    let rows: Vec<ResultRow> =
+        sqlx::query_as(include_str!("queries/get_puzzle_results_between.sql"))
+            .bind(guild)
+            .bind(local_midnight(yesterday, timezone)?)
+            .bind(local_midnight(today, timezone)?)
+            .fetch_all(&ctx.db)
+            .await?;
+
+    // quiet days are skipped, but still marked so we don't look again until
+    // tomorrow.
+    if !rows.is_empty() {
+        let channel = serenity::ChannelId(channel.parse()?);
+        info!("Posting puzzle summary in channel '{channel}'");
+        for content in summarise(&rows) {
+            let sent = channel
+                .send_message(&ctx.ctx.http, |message| {
+                    message
+                        .content(content)
+                        .allowed_mentions(|mentions| mentions.empty_parse())
+                })
+                .await;
+
+            // retrying something discord turned down would only fail again
+            // every few minutes for the rest of the day.
+            if let Err(e) = sent {
+                let e = ErrReport::from(e);
+                if !is_rejected(&e) {
+                    return Err(e);
+                }
+                error!(
+                    "Discord rejected the puzzle summary for guild {guild}, skipping today: {e}"
+                );
+                break;
+            }
+        }
+    }
+
+    sqlx::query(include_str!("queries/mark_puzzle_summary_posted.sql"))
+        .bind(today)
+        .bind(guild)
+        .execute(&ctx.db)
+        .await?;
+
+    Ok(())
+}
+
+fn local_midnight(date: NaiveDate, timezone: Tz) -> Result<DateTime<Utc>> {
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| eyre!("no midnight on {date}"))?;
+    timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .ok_or_else(|| eyre!("midnight on {date} doesn't exist in {timezone}"))
+}
+
+/// Whether discord turned a request down in a way that retrying won't fix,
+/// e.g. the channel was deleted or we can no longer post in it.
+fn is_rejected(e: &ErrReport) -> bool {
+    let Some(serenity::SerenityError::Http(e)) = e.downcast_ref() else {
+        return false;
+    };
+    matches!(
+        e.as_ref(),
+        serenity::HttpError::UnsuccessfulRequest(response) if response.status_code.is_client_error()
+    )
+}
+
+/// Mentions the first few members, and counts the rest.
+fn mention_list<'a>(users: impl Iterator<Item = &'a str>) -> String {
+    let users = users.collect_vec();
+    let mut list = users
+        .iter()
+        .take(SUMMARY_MENTIONS)
+        .map(|user| format!("<@{user}>"))
+        .join(", ");
+    if users.len() > SUMMARY_MENTIONS {
+        list.push_str(&format!(" and {} more", users.len() - SUMMARY_MENTIONS));
+    }
+    list
+}
+
+/// One line per game with who played, the best score and who snailed, split
+/// into as many messages as it takes.
+fn summarise(rows: &[ResultRow]) -> Vec<String> {
+    let games = rows
+        .iter()
+        .group_by(|(game, _, _, _)| game.as_str())
+        .into_iter()
+        .map(|(game, results)| {
+            let results = results.collect_vec();
+
+            let players = mention_list(results.iter().map(|(_, user, _, _)| user.as_str()));
+
+            let scores = results
+                .iter()
+                .filter_map(|(_, _, score, failed)| score.filter(|_| !failed));
+            let best = if results::higher_is_better(game) {
+                scores.max()
+            } else {
+                scores.min()
+            };
+
+            let snails = mention_list(
+                results
+                    .iter()
+                    .filter(|(_, _, _, failed)| *failed)
+                    .map(|(_, user, _, _)| user.as_str()),
+            );
+
+            let mut line = format!("**{game}**: {players}");
+            if let Some(best) = best {
+                line.push_str(&format!(", best score {best}"));
+            }
+            if !snails.is_empty() {
+                line.push_str(&format!(", :snail: {snails}"));
+            }
+            line
+        })
+        .collect_vec();
+
+    let mut messages = vec!["Yesterday's puzzles :newspaper:".to_owned()];
+    for line in games {
+        match messages.last_mut() {
+            Some(message) if message.len() + 1 + line.len() <= MESSAGE_LIMIT => {
+                message.push('\n');
+                message.push_str(&line);
+            }
+            _ => messages.push(line),
+        }
+    }
+    messages
+}
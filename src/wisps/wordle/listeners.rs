@@ -1,92 +1,259 @@
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
 use color_eyre::Result;
-use lazy_static::lazy_static;
+use itertools::Itertools;
 use poise::serenity_prelude as serenity;
-use regex::{Regex, RegexBuilder};
+use sqlx::SqliteExecutor;
+use tracing::{error, info, warn};
+
+use super::{
+    results::{self, PuzzleResult},
+    streaks,
+};
+use crate::Bot;
+
+pub async fn dispatch(ctx: &serenity::Context, bot: &Bot, msg: &serenity::Message) -> Result<()> {
+    // including our own summaries, which quote other members' results.
+    if msg.author.bot {
+        return Ok(());
+    }
+
+    let (result, reactions) =
+        evaluate(bot, msg.guild_id, &msg.content, msg.timestamp.date_naive()).await?;
+
+    // the result is recorded first so a reaction we can't add doesn't lose it.
+    let recorded = match &result {
+        Some(result) => record_result(msg, result, &bot.db)
+            .await
+            .unwrap_or_else(|e| {
+                error!("Failed to record puzzle result: {e}");
+                false
+            }),
+        None => false,
+    };
 
-struct Reaction {
-    pattern: Regex,
-    emoji: String,
+    react(ctx, msg, reactions).await;
+
+    if let (Some(result), true) = (result, recorded) {
+        if let Err(e) = celebrate_streak(ctx, bot, msg, &result).await {
+            error!("Failed to check puzzle streak: {e}");
+        }
+    }
+
+    Ok(())
 }
 
-fn react(pattern: &str, emoji: &str) -> Reaction {
-    Reaction {
-        #[allow(clippy::expect_used)] // skill issue
-        pattern: RegexBuilder::new(pattern)
-            .case_insensitive(true)
-            .multi_line(true)
-            .build()
-            .expect("failed to compile regex: {pattern}"),
-        emoji: emoji.to_owned(),
+/// Brings the stored result and reactions in line with an edited message.
+///
+/// Only the edited post is looked at, so if it was the first of two posts of
+/// the same puzzle, the other post isn't recorded in its place. Messages that
+/// were never results are only fetched if they now match something, so a
+/// custom rule's reaction stays after an edit stops it matching.
+pub async fn dispatch_edit(
+    ctx: &serenity::Context,
+    bot: &Bot,
+    event: &serenity::MessageUpdateEvent,
+) -> Result<()> {
+    // edits that don't touch the content, like embeds loading, can't change
+    // the result.
+    let Some(content) = &event.content else {
+        return Ok(());
+    };
+
+    if event.author.as_ref().is_some_and(|author| author.bot) {
+        return Ok(());
     }
+
+    let posted_at = event
+        .timestamp
+        .map_or_else(Utc::now, |timestamp| *timestamp);
+    let (result, wanted) = evaluate(bot, event.guild_id, content, posted_at.date_naive()).await?;
+
+    // most edits are ordinary chat, so only fetch the message if it is or was
+    // something we care about.
+    if result.is_none() && wanted.is_empty() && !has_result(event.id, &bot.db).await? {
+        return Ok(());
+    }
+
+    let mut msg = event.channel_id.message(&ctx.http, event.id).await?;
+    // messages fetched over REST don't say which guild they're in.
+    msg.guild_id = event.guild_id;
+
+    let mut tx = bot.db.begin().await?;
+    forget_result(msg.id, &mut tx).await?;
+    if let Some(result) = &result {
+        record_result(&msg, result, &mut tx).await?;
+    }
+    tx.commit().await?;
+
+    let milestones = streaks::MILESTONES
+        .iter()
+        .map(|(_, emoji)| serenity::ReactionType::Unicode((*emoji).to_owned()))
+        .collect_vec();
+    let ours = msg
+        .reactions
+        .iter()
+        .filter(|reaction| reaction.me)
+        .map(|reaction| reaction.reaction_type.clone())
+        .collect_vec();
+
+    for reaction in &ours {
+        // milestones stay as long as the post is still a result.
+        let keep = wanted.contains(reaction) || (result.is_some() && milestones.contains(reaction));
+        if !keep {
+            if let Err(e) = msg.delete_reaction(&ctx.http, None, reaction.clone()).await {
+                warn!("Failed to remove {reaction} from message {}: {e}", msg.id);
+            }
+        }
+    }
+
+    let missing = wanted
+        .into_iter()
+        .filter(|reaction| !ours.contains(reaction));
+    react(ctx, &msg, missing).await;
+
+    Ok(())
 }
 
-lazy_static! {
-    static ref REACTIONS: Vec<Reaction> = vec![
-        react(r"wordle \d+ [1-6]/6", "🧠"),
-        react(r"wordle \d+ 1/6", "1️⃣"),
-        react(r"wordle \d+ 2/6", "2️⃣"),
-        react(r"wordle \d+ X/6", "🐌"),
-        react(r"daily duotrigordle #\d+\nguesses: \d+/37", "🧠"),
-        react(r"daily duotrigordle #\d+\nguesses: X/37", "🐌"),
-        react(r"scholardle \d+ [1-6]/6", "🎓"),
-        react(r"scholardle \d+ 1/6", "1️⃣"),
-        react(r"scholardle \d+ 2/6", "2️⃣"),
-        react(r"scholardle \d+ X/6", "🐌"),
-        react(r"worldle #\d+ [1-6]/6 \(100%\)", "🗺️"),
-        react(r"worldle #\d+ X/6 \(\d+%\)", "🐌"),
-        react(r"waffle\d+ [0-5]/5", "🧇"),
-        react(r"waffle\d+ 5/5", "⭐"),
-        react(r"waffle\d+ X/5", "🐌"),
-        react(r"#wafflesilverteam", "🥈"),
-        react(r"#wafflegoldteam", "🥇"),
-        react(r"#wafflecenturion", "🌟"),
-        react(r"#wafflemaster", "🏆"),
-        react(r"flowdle \d+ \[\d+ moves\]", "🚰"),
-        react(r"flowdle \d+ \[failed\]", "🐌"),
-        react(r"jurassic wordle \(game #\d+\) - [1-8] / 8", "🦕"),
-        react(r"jurassic wordle \(game #\d+\) - X / 8", "🐌"),
-        react(r"jungdle \(game #\d+\) - [1-8] / 8", "🦁"),
-        react(r"jungdle \(game #\d+\) - X / 8", "🐌"),
-        react(r"dogsdle \(game #\d+\) - [1-8] / 8", "🐶"),
-        react(r"dogsdle \(game #\d+\) - X / 8", "🐌"),
-        react(r"framed #\d+.*\n+.*🎥 [🟥⬛ ]*🟩", "🎬"),
-        react(r"framed #\d+.*\n+.*🎥 [🟥⬛ ]+$", "🐌"),
-        react(r"moviedle #[\d-]+.*\n+.*🎥[🟥⬜⬛️ ]*🟩", "🎬"),
-        react(r"moviedle #[\d-]+.*\n+.*🎥[🟥⬜⬛️ ]+$", "🐌"),
-        react(r"posterdle #[\d-]+.*\n+ ⌛ .*\n 🍿.+🟩", "📯"),
-        react(r"posterdle #[\d-]+.*\n+ ⌛ 0️⃣ .*\n 🍿.+🟩", "0️⃣"),
-        react(r"posterdle #[\d-]+.*\n+ ⌛ .*\n 🍿 [⬜️🟥⬛️ ]+$", "🐌"),
-        react(r"namethatride #[\d-]+.*\n+ ⌛ .*\n 🚗.+🟩", "🚙"),
-        react(r"namethatride #[\d-]+.*\n+ ⌛ .*\n 🚗 [⬜️🟥⬛️ ]+$", "🐌"),
-        react(r"heardle #\d+.*\n+.*🟩", "👂"),
-        react(r"heardle #\d+.*\n+🔇", "🐌"),
-        react(r"flaggle .*\n+.*\d+ pts", "⛳"),
-        react(r"flaggle .*\n+.*gave up", "🐌"),
-        react(r"#Polygonle \d+ [1-6]/6[^🟧]+?🟩", "🔷"),
-        react(r"#Polygonle \d+ [1-6]/6[^🟩]+?🟧", "🔶"),
-        react(r"#Polygonle \d+ X/6", "🐌"),
-        react(r"#GuessTheGame #\d+.*\n+.*🎮[🟥⬛ ]*🟩", "🎮"),
-        react(r"#GuessTheGame #\d+.*\n+.*🎮 [🟥⬛ ]+$", "🐌"),
-        react(r"https://squaredle\.app/ \d+/\d+:", "🟩"),
-        react(r"https://squaredle\.app/ .*[^📖]*📖", "📖"),
-        react(r"https://squaredle\.app/ .*[^⏱️]*⏱️", "⏱️"),
-        react(r"https://squaredle\.app/ .*[^🎯]*🎯", "🎯"),
-        react(r"https://squaredle\.app/ .*[^🔥]*🔥", "🔥"),
-        react(r"Episode #\d+\n+📺 .*🟩", "📺"),
-        react(r"Episode #\d+\n+📺 [^🟩]+$", "🐌"),
-    ];
+/// Retracts the stored result of a deleted message, if it had one.
+pub async fn dispatch_delete(bot: &Bot, message: serenity::MessageId) -> Result<()> {
+    forget_result(message, &bot.db).await
 }
 
-pub async fn dispatch(ctx: &serenity::Context, msg: &serenity::Message) -> Result<()> {
-    for reaction in REACTIONS.iter() {
-        if reaction.pattern.is_match(&msg.content) {
-            msg.react(
-                ctx.clone(),
-                serenity::ReactionType::Unicode(reaction.emoji.clone()),
-            )
-            .await?;
-            break;
+/// Adds each reaction in turn, carrying on past any that fail, e.g. a custom
+/// rule's emoji that has since been deleted.
+async fn react(
+    ctx: &serenity::Context,
+    msg: &serenity::Message,
+    reactions: impl IntoIterator<Item = serenity::ReactionType>,
+) {
+    for reaction in reactions {
+        if let Err(e) = msg.react(ctx.clone(), reaction.clone()).await {
+            warn!("Failed to react with {reaction} to message {}: {e}", msg.id);
         }
     }
+}
+
+/// Reads the message's result, if it has one, and works out every reaction it
+/// should have: the result's own, followed by any matching custom rules.
+async fn evaluate(
+    bot: &Bot,
+    guild: Option<serenity::GuildId>,
+    content: &str,
+    posted: NaiveDate,
+) -> Result<(Option<PuzzleResult>, Vec<serenity::ReactionType>)> {
+    let settings = match guild {
+        Some(guild) => bot.puzzle_settings.get(guild, &bot.db).await?,
+        None => Arc::default(),
+    };
+
+    let result = results::parse(content, posted, |game| settings.is_enabled(game));
+
+    let builtin = result
+        .iter()
+        .flat_map(PuzzleResult::reactions)
+        .map(|emoji| serenity::ReactionType::Unicode(emoji.to_owned()));
+    let custom = settings
+        .rules
+        .iter()
+        .filter(|rule| rule.pattern.is_match(content))
+        .map(|rule| rule.reaction.clone());
+    let reactions = builtin.chain(custom).collect();
+
+    Ok((result, reactions))
+}
+
+/// Reacts to the post if it brings the member's streak up to a milestone.
+async fn celebrate_streak(
+    ctx: &serenity::Context,
+    bot: &Bot,
+    msg: &serenity::Message,
+    result: &PuzzleResult,
+) -> Result<()> {
+    let Some(guild) = msg.guild_id else {
+        return Ok(());
+    };
+
+    let streaks =
+        streaks::get_streaks(guild, msg.author.id, Some(result.game.as_str()), &bot.db).await?;
+    let Some((_, streak)) = streaks.first() else {
+        return Ok(());
+    };
+
+    // catching up on an older puzzle doesn't extend the streak.
+    if streak.latest != Some(result.number) {
+        return Ok(());
+    }
+
+    if let Some((_, emoji)) = streaks::MILESTONES
+        .iter()
+        .find(|(length, _)| *length == streak.current)
+    {
+        msg.react(
+            ctx.clone(),
+            serenity::ReactionType::Unicode((*emoji).to_owned()),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Stores the result if the message was posted in a guild. Returns whether it
+/// was new, rather than a repeat of a puzzle the member already posted.
+async fn record_result(
+    msg: &serenity::Message,
+    result: &PuzzleResult,
+    executor: impl SqliteExecutor<'_>,
+) -> Result<bool> {
+    let Some(guild) = msg.guild_id else {
+        return Ok(false);
+    };
+
+    info!(
+        "Recording {} #{} result for user '{}' with badges [{}]",
+        result.game,
+        result.number,
+        msg.author.id,
+        result.badges.iter().map(|badge| &badge.name).join(", ")
+    );
+    let inserted = sqlx::query(include_str!("queries/create_puzzle_result.sql"))
+        .bind(msg.id.to_string())
+        .bind(guild.to_string())
+        .bind(msg.channel_id.to_string())
+        .bind(msg.author.id.to_string())
+        .bind(&result.game)
+        .bind(result.number)
+        .bind(result.score)
+        .bind(result.max_score)
+        .bind(result.failed)
+        .bind(result.hard_mode)
+        .bind(*msg.timestamp)
+        .execute(executor)
+        .await?;
+
+    Ok(inserted.rows_affected() > 0)
+}
+
+async fn has_result(
+    message: serenity::MessageId,
+    executor: impl SqliteExecutor<'_>,
+) -> Result<bool> {
+    let (stored,): (bool,) = sqlx::query_as(include_str!("queries/has_puzzle_result.sql"))
+        .bind(message.to_string())
+        .fetch_one(executor)
+        .await?;
+    Ok(stored)
+}
+
+async fn forget_result(
+    message: serenity::MessageId,
+    executor: impl SqliteExecutor<'_>,
+) -> Result<()> {
+    sqlx::query(include_str!("queries/delete_puzzle_result.sql"))
+        .bind(message.to_string())
+        .execute(executor)
+        .await?;
     Ok(())
 }
@@ -1 +1,7 @@
+pub mod commands;
+pub mod jobs;
 pub mod listeners;
+pub mod results;
+pub mod rules;
+pub mod settings;
+pub mod streaks;
@@ -0,0 +1,16 @@
+INSERT
+INTO puzzle_result(
+    message_id,
+    guild_id,
+    channel_id,
+    user_id,
+    game,
+    puzzle_number,
+    score,
+    max_score,
+    failed,
+    hard_mode,
+    posted_at
+)
+VALUES(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT DO NOTHING
@@ -0,0 +1,5 @@
+DELETE
+FROM
+    puzzle_result
+WHERE
+    message_id = ?
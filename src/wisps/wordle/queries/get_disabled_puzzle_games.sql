@@ -0,0 +1,6 @@
+SELECT
+    game
+FROM
+    guild_puzzle_game
+WHERE
+    guild_id = ? AND NOT enabled
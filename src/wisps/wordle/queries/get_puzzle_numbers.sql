@@ -0,0 +1,10 @@
+SELECT
+    game,
+    puzzle_number,
+    posted_at
+FROM
+    puzzle_result
+WHERE
+    guild_id = ? AND user_id = ? AND game = COALESCE(?, game)
+ORDER BY
+    game, puzzle_number
@@ -0,0 +1,11 @@
+SELECT
+    game,
+    user_id,
+    score,
+    failed
+FROM
+    puzzle_result
+WHERE
+    guild_id = ? AND posted_at >= ? AND posted_at < ?
+ORDER BY
+    game, posted_at
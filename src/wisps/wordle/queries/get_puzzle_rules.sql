@@ -0,0 +1,10 @@
+SELECT
+    id,
+    pattern,
+    emoji
+FROM
+    guild_puzzle_rule
+WHERE
+    guild_id = ?
+ORDER BY
+    id
@@ -0,0 +1,12 @@
+SELECT
+    gps.guild_id,
+    gps.channel_id,
+    gps.hour,
+    gps.last_posted,
+    COALESCE(gt.timezone, 'UTC')
+FROM
+    guild_puzzle_summary as gps
+LEFT JOIN
+    guild_timezone as gt
+ON
+    gps.guild_id = gt.guild_id
@@ -0,0 +1,8 @@
+SELECT EXISTS(
+    SELECT
+        1
+    FROM
+        puzzle_result
+    WHERE
+        message_id = ?
+)
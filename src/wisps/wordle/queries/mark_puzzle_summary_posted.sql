@@ -0,0 +1,6 @@
+UPDATE
+    guild_puzzle_summary
+SET
+    last_posted = ?
+WHERE
+    guild_id = ?
@@ -0,0 +1,9 @@
+INSERT
+INTO guild_puzzle_game(
+    guild_id,
+    game,
+    enabled
+)
+VALUES(?, ?, ?)
+ON CONFLICT(guild_id, game) DO UPDATE SET
+    enabled = excluded.enabled
@@ -0,0 +1,13 @@
+SELECT
+    user_id,
+    COUNT(*) as played,
+    SUM(NOT failed) as solved,
+    AVG(CASE WHEN NOT failed THEN score END) as average
+FROM
+    puzzle_result
+WHERE
+    guild_id = ? AND game = ? AND posted_at >= COALESCE(?, posted_at)
+GROUP BY
+    user_id
+HAVING
+    COUNT(*) >= ?
@@ -0,0 +1,7 @@
+INSERT
+INTO guild_puzzle_rule(
+    guild_id,
+    pattern,
+    emoji
+)
+VALUES(?, ?, ?)
@@ -0,0 +1,5 @@
+DELETE
+FROM
+    guild_puzzle_rule
+WHERE
+    guild_id = ? AND id = ?
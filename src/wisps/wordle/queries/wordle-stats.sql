@@ -0,0 +1,10 @@
+SELECT
+    score,
+    max_score,
+    failed
+FROM
+    puzzle_result
+WHERE
+    guild_id = ? AND user_id = ? AND game = ?
+ORDER BY
+    puzzle_number
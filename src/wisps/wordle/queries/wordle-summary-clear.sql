@@ -0,0 +1,5 @@
+DELETE
+FROM
+    guild_puzzle_summary
+WHERE
+    guild_id = ?
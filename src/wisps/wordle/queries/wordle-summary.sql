@@ -0,0 +1,10 @@
+INSERT
+INTO guild_puzzle_summary(
+    guild_id,
+    channel_id,
+    hour
+)
+VALUES(?, ?, ?)
+ON CONFLICT(guild_id) DO UPDATE SET
+    channel_id = excluded.channel_id,
+    hour = excluded.hour
@@ -0,0 +1,846 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use chrono::{Datelike, NaiveDate};
+use color_eyre::{eyre::eyre, Result};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::Deserialize;
+use tracing::{error, info};
+
+use super::rules::is_unicode_emoji;
+use crate::config;
+
+/// The reaction for a puzzle that wasn't solved.
+const SNAIL: &str = "🐌";
+
+/// The games we know about out of the box.
+const DEFAULT_GAMES: &str = include_str!("games.toml");
+
+lazy_static! {
+    static ref GAMES: RwLock<Arc<Vec<GameParser>>> = RwLock::new(Arc::new(initial_games()));
+}
+
+/// A puzzle result pulled out of a shared post.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleResult {
+    pub game: String,
+    /// The puzzle number. Games that number their puzzles by date use the
+    /// number of days since the common era instead.
+    pub number: i64,
+    /// Guesses taken, moves made, points scored, etc. Not every game has one.
+    pub score: Option<u32>,
+    pub max_score: Option<u32>,
+    pub failed: bool,
+    pub hard_mode: bool,
+    /// Anything else the post shows off, e.g. Waffle teams or Squaredle
+    /// achievements.
+    pub badges: Vec<Badge>,
+    /// The reaction for this result.
+    pub reaction: String,
+}
+
+impl PuzzleResult {
+    /// Every reaction for this result: the one for the score first, then one
+    /// for each badge in the order the game defines them.
+    pub fn reactions(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.reaction.as_str())
+            .chain(self.badges.iter().map(|badge| badge.emoji.as_str()))
+    }
+}
+
+/// Something extra shown off in a puzzle post.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Badge {
+    pub name: String,
+    pub emoji: String,
+}
+
+/// How to read the puzzle number out of a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Numbering {
+    /// A plain puzzle number.
+    Number,
+    /// An ISO date, e.g. 2023-03-03.
+    Date,
+    /// A month and day, e.g. 3/3, in the year the post was made.
+    MonthDay,
+    /// No number at all, so the day the post was made is used.
+    Posted,
+}
+
+/// A games file, as described at the top of `games.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GamesFile {
+    game: Vec<GameConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameConfig {
+    id: String,
+    emoji: String,
+    pattern: String,
+    numbering: Numbering,
+    max_score: Option<u32>,
+    #[serde(default)]
+    higher_is_better: bool,
+    #[serde(default)]
+    score_reaction: Vec<ScoreReactionConfig>,
+    #[serde(default)]
+    variant: Vec<VariantConfig>,
+    #[serde(default)]
+    badge: Vec<BadgeConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoreReactionConfig {
+    score: u32,
+    emoji: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VariantConfig {
+    pattern: String,
+    emoji: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BadgeConfig {
+    name: String,
+    pattern: String,
+    emoji: String,
+}
+
+/// Recognises one game's posts and reads the result out of them.
+struct GameParser {
+    id: String,
+    /// The reaction for a solved puzzle.
+    emoji: String,
+    pattern: Regex,
+    numbering: Numbering,
+    max_score: Option<u32>,
+    /// Whether a bigger score is a better one, e.g. Waffle stars.
+    higher_is_better: bool,
+    /// Reactions for particular scores, used instead of `emoji`.
+    score_reactions: Vec<(u32, String)>,
+    /// Reactions for solved posts matching a pattern, used instead of `emoji`.
+    variants: Vec<(Regex, String)>,
+    badges: Vec<(Regex, Badge)>,
+}
+
+fn regex(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .multi_line(true)
+        .build()
+        .map_err(|e| eyre!("invalid pattern {pattern:?}: {e}"))
+}
+
+/// Checks a reaction can actually be added, since a typo would otherwise only
+/// show up as failed reactions.
+fn emoji(emoji: String) -> Result<String> {
+    if is_unicode_emoji(&emoji) {
+        Ok(emoji)
+    } else {
+        Err(eyre!("{emoji:?} isn't an emoji"))
+    }
+}
+
+impl GameParser {
+    fn from_config(config: GameConfig) -> Result<Self> {
+        if config.id.is_empty() {
+            return Err(eyre!("every game needs an id"));
+        }
+
+        let pattern = regex(&config.pattern)?;
+        let has_number = pattern
+            .capture_names()
+            .flatten()
+            .any(|name| name == "number");
+        if config.numbering != Numbering::Posted && !has_number {
+            return Err(eyre!("the pattern needs a `number` group"));
+        }
+
+        Ok(Self {
+            id: config.id,
+            emoji: emoji(config.emoji)?,
+            pattern,
+            numbering: config.numbering,
+            max_score: config.max_score,
+            higher_is_better: config.higher_is_better,
+            score_reactions: config
+                .score_reaction
+                .into_iter()
+                .map(|reaction| Ok((reaction.score, emoji(reaction.emoji)?)))
+                .collect::<Result<_>>()?,
+            variants: config
+                .variant
+                .into_iter()
+                .map(|variant| Ok((regex(&variant.pattern)?, emoji(variant.emoji)?)))
+                .collect::<Result<_>>()?,
+            badges: config
+                .badge
+                .into_iter()
+                .map(|badge| {
+                    Ok((
+                        regex(&badge.pattern)?,
+                        Badge {
+                            name: badge.name,
+                            emoji: emoji(badge.emoji)?,
+                        },
+                    ))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    fn parse(&self, content: &str, posted: NaiveDate) -> Option<PuzzleResult> {
+        let captures = self.pattern.captures(content)?;
+        let (score, max_score, failed) = self.read_score(&captures)?;
+
+        let number = match self.numbering {
+            Numbering::Posted => i64::from(posted.num_days_from_ce()),
+            numbering => read_number(captures.name("number")?.as_str(), numbering, posted)?,
+        };
+
+        let badges = self
+            .badges
+            .iter()
+            .filter(|(pattern, _)| pattern.is_match(content))
+            .map(|(_, badge)| badge.clone())
+            .collect();
+
+        Some(PuzzleResult {
+            game: self.id.clone(),
+            number,
+            score,
+            max_score,
+            failed,
+            hard_mode: captures.name("hard").is_some(),
+            badges,
+            reaction: self.reaction(content, score, failed).to_owned(),
+        })
+    }
+
+    fn read_score(&self, captures: &Captures) -> Option<(Option<u32>, Option<u32>, bool)> {
+        if let Some(squares) = captures.name("squares") {
+            let (guess, total) = read_squares(squares.as_str());
+            return Some((guess, Some(total), guess.is_none()));
+        }
+
+        let failed = captures.name("fail").is_some();
+        match captures.name("score").map(|score| score.as_str()) {
+            Some("x" | "X") => Some((None, self.max_score, true)),
+            Some(score) => Some((Some(score.parse().ok()?), self.max_score, failed)),
+            None => Some((None, self.max_score, failed)),
+        }
+    }
+
+    fn reaction(&self, content: &str, score: Option<u32>, failed: bool) -> &str {
+        if failed {
+            return SNAIL;
+        }
+
+        let by_score = self
+            .score_reactions
+            .iter()
+            .find(|(wanted, _)| Some(*wanted) == score)
+            .map(|(_, emoji)| emoji.as_str());
+
+        let by_variant = || {
+            self.variants
+                .iter()
+                .find(|(pattern, _)| pattern.is_match(content))
+                .map(|(_, emoji)| emoji.as_str())
+        };
+
+        by_score.or_else(by_variant).unwrap_or(self.emoji.as_str())
+    }
+}
+
+/// Reads and checks a games file.
+fn parse_games(source: &str) -> Result<Vec<GameParser>> {
+    let file: GamesFile = toml::from_str(source)?;
+
+    let mut ids = HashSet::new();
+    file.game
+        .into_iter()
+        .map(|config| {
+            let id = config.id.clone();
+            if !ids.insert(id.clone()) {
+                return Err(eyre!("game {id} is defined more than once"));
+            }
+            GameParser::from_config(config).map_err(|e| eyre!("game {id}: {e}"))
+        })
+        .collect()
+}
+
+/// Loads the games file from `$PUZZLE_RULES_PATH`, or the built-in one if it
+/// isn't set.
+fn load_games() -> Result<Vec<GameParser>> {
+    match config::puzzle_rules_path() {
+        Some(path) => {
+            let source =
+                std::fs::read_to_string(&path).map_err(|e| eyre!("failed to read {path}: {e}"))?;
+            parse_games(&source).map_err(|e| eyre!("failed to load {path}: {e}"))
+        }
+        None => parse_games(DEFAULT_GAMES),
+    }
+}
+
+fn initial_games() -> Vec<GameParser> {
+    load_games().unwrap_or_else(|e| {
+        error!("Falling back to the built-in puzzle games: {e}");
+        parse_games(DEFAULT_GAMES).unwrap_or_else(|e| {
+            error!("The built-in puzzle games are invalid: {e}");
+            vec![]
+        })
+    })
+}
+
+fn games() -> Arc<Vec<GameParser>> {
+    GAMES.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Loads the games file again, keeping the current games if it's invalid.
+/// Returns how many games were loaded.
+pub fn reload() -> Result<usize> {
+    let games = load_games()?;
+    let count = games.len();
+    *GAMES.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(games);
+    info!("Reloaded {count} puzzle games");
+    Ok(count)
+}
+
+/// Finds the first enabled game that recognises the post and reads its result.
+/// `posted` is the date the post was made, used for games that only give a
+/// month and day.
+pub fn parse(
+    content: &str,
+    posted: NaiveDate,
+    enabled: impl Fn(&str) -> bool,
+) -> Option<PuzzleResult> {
+    parse_with(&games(), content, posted, enabled)
+}
+
+fn parse_with(
+    games: &[GameParser],
+    content: &str,
+    posted: NaiveDate,
+    enabled: impl Fn(&str) -> bool,
+) -> Option<PuzzleResult> {
+    games
+        .iter()
+        .filter(|game| enabled(&game.id))
+        .find_map(|game| game.parse(content, posted))
+}
+
+/// The IDs of every game we recognise.
+pub fn game_ids() -> Vec<String> {
+    games().iter().map(|game| game.id.clone()).collect()
+}
+
+/// Whether a bigger score is a better one in the given game.
+pub fn higher_is_better(game: &str) -> bool {
+    games()
+        .iter()
+        .any(|parser| parser.id == game && parser.higher_is_better)
+}
+
+fn read_number(number: &str, numbering: Numbering, posted: NaiveDate) -> Option<i64> {
+    match numbering {
+        // wordle writes big numbers like 1,234.
+        Numbering::Number => number.replace(',', "").parse().ok(),
+        Numbering::Date => NaiveDate::parse_from_str(number, "%Y-%m-%d")
+            .ok()
+            .map(|date| i64::from(date.num_days_from_ce())),
+        Numbering::MonthDay => {
+            let (month, day) = number.split_once('/')?;
+            let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+            // puzzles posted just after new year can be from the year before.
+            [posted.year(), posted.year() - 1]
+                .into_iter()
+                .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+                .find(|date| date.signed_duration_since(posted).num_days() <= 1)
+                .map(|date| i64::from(date.num_days_from_ce()))
+        }
+        Numbering::Posted => None,
+    }
+}
+
+/// Reads a row of squares like "🟥🟥🟩⬛", returning which guess was green (if
+/// any) and how many guesses there were.
+fn read_squares(squares: &str) -> (Option<u32>, u32) {
+    let mut guess = None;
+    let mut total = 0;
+    for square in squares
+        .chars()
+        .filter(|c| matches!(c, '🟥' | '🟩' | '🟨' | '⬜' | '⬛'))
+    {
+        total += 1;
+        if square == '🟩' && guess.is_none() {
+            guess = Some(total);
+        }
+    }
+    (guess, total)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn posted() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 3, 3).unwrap()
+    }
+
+    fn days(year: i32, month: u32, day: u32) -> i64 {
+        i64::from(
+            NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .num_days_from_ce(),
+        )
+    }
+
+    fn parse_sample(content: &str) -> PuzzleResult {
+        let games = parse_games(DEFAULT_GAMES).unwrap();
+        parse_with(&games, content, posted(), |_| true)
+            .unwrap_or_else(|| panic!("no game recognised {content:?}"))
+    }
+
+    fn check(
+        content: &str,
+        game: &str,
+        number: i64,
+        score: Option<u32>,
+        failed: bool,
+        reaction: &str,
+        badges: &[&str],
+    ) {
+        let result = parse_sample(content);
+        assert_eq!(result.game, game, "{content:?}");
+        assert_eq!(result.number, number, "{content:?}");
+        assert_eq!(result.score, score, "{content:?}");
+        assert_eq!(result.failed, failed, "{content:?}");
+        assert_eq!(result.reaction, reaction, "{content:?}");
+        assert_eq!(
+            result
+                .badges
+                .iter()
+                .map(|badge| badge.name.as_str())
+                .collect::<Vec<_>>(),
+            badges,
+            "{content:?}"
+        );
+    }
+
+    #[test]
+    fn wordle() {
+        check(
+            "Wordle 621 3/6*\n\n⬛🟨⬛⬛⬛\n🟩🟩⬛⬛🟩\n🟩🟩🟩🟩🟩",
+            "wordle",
+            621,
+            Some(3),
+            false,
+            "🧠",
+            &[],
+        );
+        check(
+            "Wordle 1,234 1/6\n\n🟩🟩🟩🟩🟩",
+            "wordle",
+            1234,
+            Some(1),
+            false,
+            "1️⃣",
+            &[],
+        );
+        check(
+            "Wordle 621 2/6\n\n🟩🟩⬛⬛🟩\n🟩🟩🟩🟩🟩",
+            "wordle",
+            621,
+            Some(2),
+            false,
+            "2️⃣",
+            &[],
+        );
+        check(
+            "Wordle 621 X/6\n\n⬛⬛⬛⬛⬛",
+            "wordle",
+            621,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+        assert!(parse_sample("Wordle 621 3/6*").hard_mode);
+    }
+
+    #[test]
+    fn duotrigordle() {
+        check(
+            "Daily Duotrigordle #356\nGuesses: 35/37\nTime: 12:34",
+            "duotrigordle",
+            356,
+            Some(35),
+            false,
+            "🧠",
+            &[],
+        );
+        check(
+            "Daily Duotrigordle #356\nGuesses: X/37\nTime: 12:34",
+            "duotrigordle",
+            356,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn scholardle() {
+        check(
+            "Scholardle 412 4/6\n\n⬛🟨⬛⬛⬛⬛\n🟩🟩🟩🟩🟩🟩",
+            "scholardle",
+            412,
+            Some(4),
+            false,
+            "🎓",
+            &[],
+        );
+    }
+
+    #[test]
+    fn worldle() {
+        check(
+            "#Worldle #412 3/6 (100%)\n🟩🟩🟩🟨⬜↗️\n🟩🟩🟩🟩🟨↖️\n🟩🟩🟩🟩🟩🎉",
+            "worldle",
+            412,
+            Some(3),
+            false,
+            "🗺️",
+            &[],
+        );
+        check(
+            "#Worldle #412 X/6 (87%)",
+            "worldle",
+            412,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn waffle() {
+        check(
+            "#waffle407 3/5\n\n🟩🟩🟩🟩🟩\n🟩⭐🟩⭐🟩\n🟩🟩⭐🟩🟩\n🟩⬜🟩⬜🟩\n🟩🟩🟩🟩🟩\n\n🔥 streak: 12\nwafflegame.net",
+            "waffle",
+            407,
+            Some(3),
+            false,
+            "🧇",
+            &[],
+        );
+        check(
+            "#waffle407 5/5\n\n🔥 streak: 100\n#wafflegoldteam #wafflecenturion",
+            "waffle",
+            407,
+            Some(5),
+            false,
+            "⭐",
+            &["gold-team", "centurion"],
+        );
+        check("#waffle407 0/5", "waffle", 407, Some(0), false, "🧇", &[]);
+        check("#waffle407 X/5", "waffle", 407, None, true, SNAIL, &[]);
+    }
+
+    #[test]
+    fn flowdle() {
+        check(
+            "Flowdle 312 [14 moves]\n🟩🟩🟩",
+            "flowdle",
+            312,
+            Some(14),
+            false,
+            "🚰",
+            &[],
+        );
+        check(
+            "Flowdle 312 [failed]",
+            "flowdle",
+            312,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn dinosaurs_lions_and_dogs() {
+        check(
+            "Jurassic Wordle (Game #301) - 4 / 8\n⬜🟨⬜⬜⬜",
+            "jurassic-wordle",
+            301,
+            Some(4),
+            false,
+            "🦕",
+            &[],
+        );
+        check(
+            "Jungdle (Game #198) - X / 8",
+            "jungdle",
+            198,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+        check(
+            "Dogsdle (Game #87) - 2 / 8",
+            "dogsdle",
+            87,
+            Some(2),
+            false,
+            "🐶",
+            &[],
+        );
+    }
+
+    #[test]
+    fn framed() {
+        check(
+            "Framed #356\n🎥 🟥 🟥 🟩 ⬛ ⬛ ⬛\n\nhttps://framed.wtf",
+            "framed",
+            356,
+            Some(3),
+            false,
+            "🎬",
+            &[],
+        );
+        check(
+            "Framed #356\n🎥 🟥 🟥 🟥 🟥 🟥 🟥\n\nhttps://framed.wtf",
+            "framed",
+            356,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn moviedle() {
+        check(
+            "#Moviedle #2023-03-03 \n\n 🎥 🟥 🟥 🟩 ⬜️ ⬜️ ⬜️ \n\n https://likewise.com/games/moviedle/2023-03-03",
+            "moviedle",
+            days(2023, 3, 3),
+            Some(3),
+            false,
+            "🎬",
+            &[],
+        );
+    }
+
+    #[test]
+    fn posterdle() {
+        check(
+            "#Posterdle #2023-03-03 \n\n ⌛ 1️⃣ 4️⃣s \n 🍿 🟥 🟩 ⬜️ ⬜️ ⬜️ ⬜️ \n\n https://likewise.com/games/posterdle/2023-03-03",
+            "posterdle",
+            days(2023, 3, 3),
+            Some(2),
+            false,
+            "📯",
+            &[],
+        );
+        check(
+            "#Posterdle #2023-03-03 \n\n ⌛ 0️⃣ 8️⃣s \n 🍿 🟩 ⬜️ ⬜️ ⬜️ ⬜️ ⬜️ \n\n https://likewise.com/games/posterdle/2023-03-03",
+            "posterdle",
+            days(2023, 3, 3),
+            Some(1),
+            false,
+            "0️⃣",
+            &[],
+        );
+    }
+
+    #[test]
+    fn namethatride() {
+        check(
+            "#NameThatRide #2023-03-03 \n\n ⌛ 2️⃣ 1️⃣s \n 🚗 🟥 🟥 🟥 🟥 🟥 🟥 \n\n https://likewise.com/games/namethatride/2023-03-03",
+            "namethatride",
+            days(2023, 3, 3),
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn heardle() {
+        check(
+            "#Heardle #412\n\n🔉⬛️⬛️🟩⬜️⬜️⬜️\n\n#ProudToHeardle",
+            "heardle",
+            412,
+            Some(3),
+            false,
+            "👂",
+            &[],
+        );
+        check(
+            "#Heardle #412\n\n🔇⬛️⬛️⬛️⬛️⬛️⬛️\n\n#ProudToHeardle",
+            "heardle",
+            412,
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn flaggle() {
+        check(
+            "Flaggle 🇫🇷 3/3\n\n🟢🟢🟡 850 pts",
+            "flaggle",
+            days(2023, 3, 3),
+            Some(850),
+            false,
+            "⛳",
+            &[],
+        );
+        check(
+            "Flaggle 🇫🇷 3/3\n\n🔴🔴🔴 gave up",
+            "flaggle",
+            days(2023, 3, 3),
+            None,
+            true,
+            SNAIL,
+            &[],
+        );
+    }
+
+    #[test]
+    fn polygonle() {
+        check(
+            "#Polygonle 402 2/6\n🟩🟩🟧⬜\n🟩🟩🟩🟩",
+            "polygonle",
+            402,
+            Some(2),
+            false,
+            "🔷",
+            &[],
+        );
+        check(
+            "#Polygonle 402 3/6\n🟧🟧⬜⬜\n🟩🟧🟧⬜\n🟩🟩🟩🟩",
+            "polygonle",
+            402,
+            Some(3),
+            false,
+            "🔶",
+            &[],
+        );
+    }
+
+    #[test]
+    fn guessthegame() {
+        check(
+            "#GuessTheGame #358\n\n🎮 🟥 🟥 🟩 ⬜ ⬜ ⬜\n\n#ProudOfMyGameKnowledge",
+            "guessthegame",
+            358,
+            Some(3),
+            false,
+            "🎮",
+            &[],
+        );
+    }
+
+    #[test]
+    fn squaredle() {
+        check(
+            "I played https://squaredle.app/ 3/2: 📖 ⏱️ 🔥 12\nWords: 31/31",
+            "squaredle",
+            days(2023, 3, 2),
+            None,
+            false,
+            "🟩",
+            &["word-of-the-day", "speedy", "streak"],
+        );
+        // puzzles posted just after new year are from the year before.
+        let result = parse_with(
+            &parse_games(DEFAULT_GAMES).unwrap(),
+            "I played https://squaredle.app/ 12/31:",
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            |_| true,
+        )
+        .unwrap();
+        assert_eq!(result.number, days(2023, 12, 31));
+    }
+
+    #[test]
+    fn episode() {
+        check(
+            "Episode #123\n\n📺 🟥 🟩 ⬜ ⬜ ⬜ ⬜",
+            "episode",
+            123,
+            Some(2),
+            false,
+            "📺",
+            &[],
+        );
+    }
+
+    #[test]
+    fn default_games_load() {
+        let games = parse_games(DEFAULT_GAMES).unwrap();
+        assert!(!games.is_empty());
+    }
+
+    #[test]
+    fn games_need_real_emoji() {
+        let game = |emoji: &str| {
+            format!(
+                r#"
+                [[game]]
+                id = "test"
+                emoji = "{emoji}"
+                pattern = 'test (?P<number>\d+)'
+                numbering = "number"
+                "#
+            )
+        };
+        assert!(parse_games(&game("🧪")).is_ok());
+        assert!(parse_games(&game(":test:")).is_err());
+        assert!(parse_games(&game("")).is_err());
+    }
+
+    #[test]
+    fn disabled_games_are_skipped() {
+        let games = parse_games(DEFAULT_GAMES).unwrap();
+        let post = "Wordle 621 3/6";
+        assert!(parse_with(&games, post, posted(), |game| game != "wordle").is_none());
+    }
+
+    #[test]
+    fn other_posts_are_ignored() {
+        let games = parse_games(DEFAULT_GAMES).unwrap();
+        for post in ["hello", "I got wordle in 3 today", "Wordle 621 7/6"] {
+            assert!(
+                parse_with(&games, post, posted(), |_| true).is_none(),
+                "{post}"
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use color_eyre::{eyre::eyre, Result};
+use lazy_static::lazy_static;
+use poise::serenity_prelude as serenity;
+use regex::{Regex, RegexBuilder};
+
+/// How many custom rules each guild can have.
+pub const MAX_RULES_PER_GUILD: usize = 25;
+
+/// How many characters long a custom rule's pattern can be.
+pub const MAX_PATTERN_LENGTH: usize = 200;
+
+/// How big a custom rule's compiled pattern can get, in bytes. Keeps admins
+/// from making the listener slow with huge patterns.
+const MAX_PATTERN_SIZE: usize = 1 << 16;
+
+#[allow(clippy::expect_used)] // pattern is constant
+fn custom_emoji_regex() -> Regex {
+    Regex::new(r"^<(a?):(\w+):(\d+)>$").expect("failed to compile custom emoji regex")
+}
+
+lazy_static! {
+    static ref CUSTOM_EMOJI: Regex = custom_emoji_regex();
+}
+
+/// A guild's own pattern → emoji rule.
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    pub id: i64,
+    pub pattern: Regex,
+    pub reaction: serenity::ReactionType,
+}
+
+impl CustomRule {
+    /// Compiles a stored rule, checking it the same way as a new one.
+    pub fn new(id: i64, pattern: &str, emoji: &str) -> Result<Self> {
+        Ok(Self {
+            id,
+            pattern: compile_pattern(pattern)?,
+            reaction: parse_emoji(emoji)?,
+        })
+    }
+}
+
+/// Compiles a pattern the same way the built-in games are, within size limits.
+pub fn compile_pattern(pattern: &str) -> Result<Regex> {
+    if pattern.chars().count() > MAX_PATTERN_LENGTH {
+        return Err(eyre!(
+            "patterns can't be longer than {MAX_PATTERN_LENGTH} characters"
+        ));
+    }
+
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .multi_line(true)
+        .size_limit(MAX_PATTERN_SIZE)
+        .dfa_size_limit(MAX_PATTERN_SIZE)
+        .build()
+        .map_err(|e| eyre!("{e}"))
+}
+
+/// Reads either a custom guild emoji like `<:name:123>` or a plain emoji.
+pub fn parse_emoji(emoji: &str) -> Result<serenity::ReactionType> {
+    let emoji = emoji.trim();
+
+    if let Some(captures) = CUSTOM_EMOJI.captures(emoji) {
+        return Ok(serenity::ReactionType::Custom {
+            animated: !captures[1].is_empty(),
+            id: serenity::EmojiId(captures[3].parse()?),
+            name: Some(captures[2].to_owned()),
+        });
+    }
+
+    if !is_unicode_emoji(emoji) {
+        return Err(eyre!("that doesn't look like an emoji"));
+    }
+
+    Ok(serenity::ReactionType::Unicode(emoji.to_owned()))
+}
+
+/// The character that turns a digit, `#` or `*` into a keycap emoji like 1️⃣.
+const KEYCAP: char = '\u{20e3}';
+
+/// Whether the text looks like a single plain emoji. Emoji can be several code
+/// points long, e.g. flags, keycaps, skin tones and families, but not very
+/// many.
+pub fn is_unicode_emoji(emoji: &str) -> bool {
+    let keycap = emoji.ends_with(KEYCAP);
+    let allowed = |c: char| {
+        is_pictographic(c)
+            || matches!(
+                c,
+                '\u{200d}' | '\u{fe0f}' | KEYCAP | '\u{e0020}'..='\u{e007f}'
+            )
+            || (keycap && matches!(c, '0'..='9' | '#' | '*'))
+    };
+
+    (1..=8).contains(&emoji.chars().count())
+        && (keycap || emoji.chars().any(is_pictographic))
+        && emoji.chars().all(allowed)
+}
+
+/// Roughly Unicode's `Extended_Pictographic` property: the characters emoji are
+/// drawn from. Flags and skin tones are in the big block at the end.
+const fn is_pictographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0xa9 | 0xae
+            | 0x203c
+            | 0x2049
+            | 0x2122
+            | 0x2139
+            | 0x2194..=0x2199
+            | 0x21a9..=0x21aa
+            | 0x231a..=0x231b
+            | 0x2328
+            | 0x2388
+            | 0x23cf
+            | 0x23e9..=0x23f3
+            | 0x23f8..=0x23fa
+            | 0x24c2
+            | 0x25aa..=0x25ab
+            | 0x25b6
+            | 0x25c0
+            | 0x25fb..=0x25fe
+            | 0x2600..=0x27bf
+            | 0x2934..=0x2935
+            | 0x2b05..=0x2b07
+            | 0x2b1b..=0x2b1c
+            | 0x2b50
+            | 0x2b55
+            | 0x3030
+            | 0x303d
+            | 0x3297
+            | 0x3299
+            | 0x1f000..=0x1faff
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_emoji() {
+        for emoji in [
+            "🧠",
+            "⭐",
+            "🗺️",
+            "1️⃣",
+            "#️⃣",
+            "🇬🇧",
+            "👍🏽",
+            "👨‍👩‍👧‍👦",
+            "🏳️‍🌈",
+            "‼️",
+            "©️",
+        ] {
+            assert!(is_unicode_emoji(emoji), "{emoji}");
+        }
+    }
+
+    #[test]
+    fn not_emoji() {
+        for text in [
+            "",
+            "a",
+            "1",
+            "!!",
+            "→",
+            ":smile:",
+            "🧠 brain",
+            "1️⃣a",
+            "🧠🧠🧠🧠🧠🧠🧠🧠🧠",
+        ] {
+            assert!(!is_unicode_emoji(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn custom_emoji() {
+        assert_eq!(
+            parse_emoji("<a:party:123>").ok(),
+            Some(serenity::ReactionType::Custom {
+                animated: true,
+                id: serenity::EmojiId(123),
+                name: Some("party".to_owned()),
+            })
+        );
+        assert!(parse_emoji("<:party:abc>").is_err());
+    }
+
+    #[test]
+    fn pattern_length_counts_characters() {
+        assert!(compile_pattern(&"🧠".repeat(MAX_PATTERN_LENGTH)).is_ok());
+        assert!(compile_pattern(&"a".repeat(MAX_PATTERN_LENGTH + 1)).is_err());
+    }
+}
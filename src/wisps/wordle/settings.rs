@@ -0,0 +1,108 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use color_eyre::Result;
+use poise::serenity_prelude as serenity;
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use super::rules::CustomRule;
+
+/// A guild's puzzle settings.
+#[derive(Debug, Default)]
+pub struct PuzzleSettings {
+    pub disabled_games: HashSet<String>,
+    pub rules: Vec<CustomRule>,
+}
+
+impl PuzzleSettings {
+    pub fn is_enabled(&self, game: &str) -> bool {
+        !self.disabled_games.contains(game)
+    }
+
+    async fn load(guild: serenity::GuildId, pool: &SqlitePool) -> Result<Self> {
+        let disabled: Vec<(String,)> =
+            sqlx::query_as(include_str!("queries/get_disabled_puzzle_games.sql"))
+                .bind(guild.to_string())
+                .fetch_all(pool)
+                .await?;
+
+        let rows: Vec<(i64, String, String)> =
+            sqlx::query_as(include_str!("queries/get_puzzle_rules.sql"))
+                .bind(guild.to_string())
+                .fetch_all(pool)
+                .await?;
+
+        let rules = rows
+            .into_iter()
+            .filter_map(|(id, pattern, emoji)| {
+                CustomRule::new(id, &pattern, &emoji)
+                    .map_err(|e| warn!("Skipping invalid puzzle rule {id}: {e}"))
+                    .ok()
+            })
+            .collect();
+
+        Ok(Self {
+            disabled_games: disabled.into_iter().map(|(game,)| game).collect(),
+            rules,
+        })
+    }
+}
+
+/// Keeps each guild's puzzle settings in memory so the listener doesn't have
+/// to query them for every message. Anything that changes the settings must
+/// call `invalidate`.
+#[derive(Debug, Default)]
+pub struct PuzzleSettingsCache {
+    guilds: RwLock<CachedGuilds>,
+}
+
+#[derive(Debug, Default)]
+struct CachedGuilds {
+    settings: HashMap<serenity::GuildId, Arc<PuzzleSettings>>,
+    /// Bumped by every `invalidate`, so a load that raced one can tell its
+    /// settings may already be stale.
+    generations: HashMap<serenity::GuildId, u64>,
+}
+
+impl CachedGuilds {
+    fn generation(&self, guild: serenity::GuildId) -> u64 {
+        self.generations.get(&guild).copied().unwrap_or_default()
+    }
+}
+
+impl PuzzleSettingsCache {
+    pub async fn get(
+        &self,
+        guild: serenity::GuildId,
+        pool: &SqlitePool,
+    ) -> Result<Arc<PuzzleSettings>> {
+        let generation = {
+            let guilds = self.guilds.read().await;
+            if let Some(settings) = guilds.settings.get(&guild) {
+                return Ok(settings.clone());
+            }
+            guilds.generation(guild)
+        };
+
+        // loaded without the lock so other guilds aren't held up behind the
+        // query. if the settings were invalidated meanwhile they're used this
+        // once but not cached.
+        let settings = Arc::new(PuzzleSettings::load(guild, pool).await?);
+
+        let mut guilds = self.guilds.write().await;
+        if guilds.generation(guild) == generation {
+            guilds.settings.insert(guild, settings.clone());
+        }
+        Ok(settings)
+    }
+
+    pub async fn invalidate(&self, guild: serenity::GuildId) {
+        let mut guilds = self.guilds.write().await;
+        guilds.settings.remove(&guild);
+        *guilds.generations.entry(guild).or_default() += 1;
+    }
+}
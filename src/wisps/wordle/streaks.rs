@@ -0,0 +1,83 @@
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::Result;
+use itertools::Itertools;
+use poise::serenity_prelude as serenity;
+use sqlx::SqlitePool;
+
+use crate::config;
+
+/// Streak lengths that get a special reaction, and the reaction.
+pub const MILESTONES: [(u32, &str); 3] = [(7, "🔥"), (30, "🏅"), (100, "💯")];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    /// How many puzzles in a row the member has posted, up to the latest one.
+    /// Zero if they've missed a puzzle since.
+    pub current: u32,
+    pub best: u32,
+    /// The latest puzzle number the member has posted.
+    pub latest: Option<i64>,
+}
+
+/// Works out a streak from a member's results for one game, given as puzzle
+/// numbers and when they were posted, sorted by puzzle number.
+///
+/// The current streak survives as long as the latest result was posted within
+/// a day of now, plus the grace period for late posts.
+pub fn compute(results: &[(i64, DateTime<Utc>)], now: DateTime<Utc>, grace: Duration) -> Streak {
+    let mut streak = Streak::default();
+    let mut run = 0;
+    let mut previous = None;
+
+    for &(number, _) in results {
+        run = match previous {
+            Some(previous) if number == previous => continue,
+            Some(previous) if number == previous + 1 => run + 1,
+            _ => 1,
+        };
+        streak.best = streak.best.max(run);
+        previous = Some(number);
+    }
+    streak.latest = previous;
+
+    let alive = results.last().map_or(false, |(_, posted_at)| {
+        *posted_at >= now - Duration::days(1) - grace
+    });
+    if alive {
+        streak.current = run;
+    }
+
+    streak
+}
+
+/// A member's streak for every game they've played in the guild, or just the
+/// given one.
+pub async fn get_streaks(
+    guild: serenity::GuildId,
+    user: serenity::UserId,
+    game: Option<&str>,
+    pool: &SqlitePool,
+) -> Result<Vec<(String, Streak)>> {
+    let rows: Vec<(String, i64, DateTime<Utc>)> =
+        sqlx::query_as(include_str!("queries/get_puzzle_numbers.sql"))
+            .bind(guild.to_string())
+            .bind(user.to_string())
+            .bind(game)
+            .fetch_all(pool)
+            .await?;
+
+    let now = Utc::now();
+    let grace = Duration::hours(config::puzzle_streak_grace_hours());
+
+    Ok(rows
+        .into_iter()
+        .group_by(|(game, _, _)| game.clone())
+        .into_iter()
+        .map(|(game, rows)| {
+            let results = rows
+                .map(|(_, number, posted_at)| (number, posted_at))
+                .collect_vec();
+            (game, compute(&results, now, grace))
+        })
+        .collect())
+}